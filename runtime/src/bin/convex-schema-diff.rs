@@ -0,0 +1,59 @@
+//! Compares two validator source files and reports every change.
+//!
+//! ```sh
+//! convex-schema-diff old.ts new.ts [--json]
+//! ```
+//!
+//! Exits with 1 when any change breaks readers of either schema.
+
+use std::process::ExitCode;
+
+use ragkit_convex_runtime::{diff, Compatibility, Validator};
+
+fn main() -> ExitCode {
+  let mut json = false;
+  let mut paths = Vec::new();
+  for arg in std::env::args().skip(1) {
+    match arg.as_str() {
+      | "--json" => json = true,
+      | _ => paths.push(arg),
+    }
+  }
+  let [old_path, new_path] = paths.as_slice() else {
+    eprintln!("Usage: convex-schema-diff <old> <new> [--json]");
+    return ExitCode::from(2);
+  };
+
+  let (old, new) = match (read_validator(old_path), read_validator(new_path)) {
+    | (Ok(old), Ok(new)) => (old, new),
+    | (Err(error), _) | (_, Err(error)) => {
+      eprintln!("{}", error);
+      return ExitCode::from(2);
+    },
+  };
+
+  let changes = diff(&old, &new);
+  if json {
+    let report: Vec<_> =
+      changes.iter().map(|change| change.to_json()).collect();
+    println!("{}", serde_json::Value::from(report));
+  } else {
+    for change in &changes {
+      println!("{}", change);
+    }
+  }
+
+  let breaking =
+    changes.iter().any(|change| change.compatibility() != Compatibility::Safe);
+  if breaking {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
+  }
+}
+
+fn read_validator(path: &str) -> Result<Validator, String> {
+  let source = std::fs::read_to_string(path)
+    .map_err(|error| format!("{}: {}", path, error))?;
+  Validator::parse(&source).map_err(|error| format!("{}: {}", path, error))
+}
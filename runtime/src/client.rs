@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+
+use convex::{ConvexClient, ConvexError, FunctionResult, Value};
+
+/// The calls generated functions make, implemented for [`ConvexClient`].
+///
+/// Implement this for a local in-memory stand-in to test code using generated
+/// functions without a deployment.
+pub trait FunctionClient {
+  fn query(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+
+  fn mutation(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+
+  fn action(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+}
+
+impl FunctionClient for ConvexClient {
+  fn query(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    ConvexClient::query(self, name, args)
+  }
+
+  fn mutation(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    ConvexClient::mutation(self, name, args)
+  }
+
+  fn action(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    ConvexClient::action(self, name, args)
+  }
+}
+
+/// Every way calling a generated function can fail.
+#[derive(Debug)]
+pub enum FunctionError {
+  /// The client could not run the function, e.g. the connection failed.
+  Client(anyhow::Error),
+  /// The function failed with an error message.
+  Message(String),
+  /// The function threw a `ConvexError` with application data.
+  Convex(ConvexError),
+  /// The returned value did not match the `returns` validator.
+  Decode(anyhow::Error),
+}
+
+impl fmt::Display for FunctionError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | FunctionError::Client(error) => write!(f, "Client error: {}", error),
+      | FunctionError::Message(message) => write!(f, "{}", message),
+      | FunctionError::Convex(error) => write!(f, "{}", error),
+      | FunctionError::Decode(error) => {
+        write!(f, "Invalid return value: {}", error)
+      },
+    }
+  }
+}
+
+impl std::error::Error for FunctionError {
+}
+
+/// Extracts the value of a successful [`FunctionResult`].
+pub fn into_value(result: FunctionResult) -> Result<Value, FunctionError> {
+  match result {
+    | FunctionResult::Value(value) => Ok(value),
+    | FunctionResult::ErrorMessage(message) => {
+      Err(FunctionError::Message(message))
+    },
+    | FunctionResult::ConvexError(error) => Err(FunctionError::Convex(error)),
+  }
+}
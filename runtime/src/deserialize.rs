@@ -0,0 +1,116 @@
+//! Helpers for the `Deserialize` impls of generated models.
+
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes a present value into `Some`, so with `#[serde(default)]` an
+/// `Option<Option<T>>` tells a missing field (`None`) from `null`
+/// (`Some(None)`).
+pub fn deserialize_some<'de, D, T>(
+  deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  T::deserialize(deserializer).map(Some)
+}
+
+/// Deserializes numbers, including the `{"$float": ...}` encoding generated
+/// models use for `NaN`, `Infinity` and `-0.0`.
+#[cfg(feature = "convex")]
+pub fn deserialize_float<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FloatField,
+{
+  let value = serde_json::Value::deserialize(deserializer)?;
+  T::from_json(value).map_err(serde::de::Error::custom)
+}
+
+/// Like [`deserialize_some`] for types read by [`deserialize_float`].
+#[cfg(feature = "convex")]
+pub fn deserialize_some_float<'de, D, T>(
+  deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FloatField,
+{
+  deserialize_float(deserializer).map(Some)
+}
+
+/// Deserializes the undeclared fields collected by a `..rest` field, decoding
+/// Convex's JSON encoding (like `{"$integer": ...}`) they are serialized with.
+#[cfg(feature = "convex")]
+pub fn deserialize_rest<'de, D>(
+  deserializer: D,
+) -> Result<std::collections::BTreeMap<String, convex::Value>, D::Error>
+where D: Deserializer<'de> {
+  let values =
+    std::collections::BTreeMap::<String, serde_json::Value>::deserialize(
+      deserializer,
+    )?;
+  values
+    .into_iter()
+    .map(|(key, value)| {
+      let value = convex::Value::try_from(value).map_err(|error| {
+        serde::de::Error::custom(format!("Invalid field '{}': {}", key, error))
+      })?;
+      Ok((key, value))
+    })
+    .collect()
+}
+
+/// A field type holding numbers that [`deserialize_float`] can read.
+#[cfg(feature = "convex")]
+pub trait FloatField: Sized {
+  fn from_json(value: serde_json::Value) -> Result<Self, String>;
+}
+
+#[cfg(feature = "convex")]
+impl FloatField for f64 {
+  fn from_json(value: serde_json::Value) -> Result<Self, String> {
+    if let Some(number) = value.as_f64() {
+      return Ok(number);
+    }
+    match convex::Value::try_from(value) {
+      | Ok(convex::Value::Float64(number)) => Ok(number),
+      | _ => Err("Expected a number".into()),
+    }
+  }
+}
+
+#[cfg(feature = "convex")]
+impl<T: FloatField> FloatField for Option<T> {
+  fn from_json(value: serde_json::Value) -> Result<Self, String> {
+    match value {
+      | serde_json::Value::Null => Ok(None),
+      | value => T::from_json(value).map(Some),
+    }
+  }
+}
+
+#[cfg(feature = "convex")]
+impl<T: FloatField> FloatField for Vec<T> {
+  fn from_json(value: serde_json::Value) -> Result<Self, String> {
+    match value {
+      | serde_json::Value::Array(values) => {
+        values.into_iter().map(T::from_json).collect()
+      },
+      | _ => Err("Expected an array".into()),
+    }
+  }
+}
+
+#[cfg(feature = "convex")]
+impl<T: FloatField> FloatField for std::collections::BTreeMap<String, T> {
+  fn from_json(value: serde_json::Value) -> Result<Self, String> {
+    match value {
+      | serde_json::Value::Object(values) => values
+        .into_iter()
+        .map(|(key, value)| Ok((key, T::from_json(value)?)))
+        .collect(),
+      | _ => Err("Expected an object".into()),
+    }
+  }
+}
@@ -0,0 +1,404 @@
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use crate::{FieldValidator, Literal, ObjectValidator, Validator};
+
+/// A difference between two schemas, see [`diff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaChange {
+  /// Where the change is, like `platform.username`, `tags[]` for array items
+  /// or `platform<github>` for a union branch.
+  pub path: String,
+  pub kind: ChangeKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeKind {
+  FieldAdded { optional: bool },
+  FieldRemoved { optional: bool },
+  FieldMadeOptional,
+  FieldMadeRequired,
+  TypeChanged { old: String, new: String },
+  LiteralChanged { old: Literal, new: Literal },
+  IdTableChanged { old: String, new: String },
+  UnionBranchAdded { branch: String },
+  UnionBranchRemoved { branch: String },
+  StrictAdded,
+  StrictRemoved,
+  RestAdded,
+  RestRemoved,
+}
+
+/// Whether readers built against either schema can still decode documents
+/// written with the other one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+  Safe,
+  /// Readers built against the old schema can't decode new documents.
+  BreaksOldReaders,
+  /// Readers built against the new schema can't decode existing documents.
+  BreaksNewReaders,
+  Breaking,
+}
+
+/// Compares two schemas and lists every change from `old` to `new`.
+pub fn diff(old: &Validator, new: &Validator) -> Vec<SchemaChange> {
+  let mut changes = Vec::new();
+  diff_path(old, new, "", &mut changes);
+  changes
+}
+
+fn diff_path(
+  old: &Validator,
+  new: &Validator,
+  path: &str,
+  changes: &mut Vec<SchemaChange>,
+) {
+  let mut change =
+    |kind: ChangeKind| changes.push(SchemaChange { path: path.into(), kind });
+  match (old, new) {
+    // Optional fields are compared by their object.
+    | (Validator::Optional(old), new) => diff_path(old, new, path, changes),
+    | (old, Validator::Optional(new)) => diff_path(old, new, path, changes),
+    | (Validator::Nullable(old), Validator::Nullable(new)) => {
+      diff_path(old, new, path, changes)
+    },
+    // Nullable values are compared with other unions as the union Convex
+    // expands them to.
+    | (Validator::Nullable(old), new) => diff_path(
+      &Validator::Union(vec![(**old).clone(), Validator::Null]),
+      new,
+      path,
+      changes,
+    ),
+    | (old, Validator::Nullable(new)) => diff_path(
+      old,
+      &Validator::Union(vec![(**new).clone(), Validator::Null]),
+      path,
+      changes,
+    ),
+    | (Validator::Id(old), Validator::Id(new)) => {
+      if old != new {
+        change(ChangeKind::IdTableChanged {
+          old: old.clone(),
+          new: new.clone(),
+        })
+      }
+    },
+    | (Validator::Literal(old), Validator::Literal(new)) => {
+      if old != new {
+        change(ChangeKind::LiteralChanged {
+          old: old.clone(),
+          new: new.clone(),
+        })
+      }
+    },
+    | (Validator::Array(old), Validator::Array(new)) => {
+      diff_path(old, new, &format!("{}[]", path), changes)
+    },
+    | (
+      Validator::Record(old_keys, old_values),
+      Validator::Record(new_keys, new_values),
+    ) => {
+      diff_path(old_keys, new_keys, &format!("{}<keys>", path), changes);
+      diff_path(old_values, new_values, &format!("{}<values>", path), changes);
+    },
+    | (Validator::Object(old), Validator::Object(new)) => {
+      diff_object(old, new, path, changes)
+    },
+    | (Validator::Union(old), Validator::Union(new)) => {
+      diff_union(old, new, path, changes)
+    },
+    | (old, new) => {
+      if old.kind_name() != new.kind_name() {
+        change(ChangeKind::TypeChanged {
+          old: old.kind_name().into(),
+          new: new.kind_name().into(),
+        })
+      }
+    },
+  }
+}
+
+fn diff_object(
+  old: &ObjectValidator,
+  new: &ObjectValidator,
+  path: &str,
+  changes: &mut Vec<SchemaChange>,
+) {
+  let field_path = |field: &FieldValidator| {
+    if path.is_empty() {
+      field.name.clone()
+    } else {
+      format!("{}.{}", path, field.name)
+    }
+  };
+  let mut change =
+    |path: String, kind: ChangeKind| changes.push(SchemaChange { path, kind });
+
+  match (old.strict, new.strict) {
+    | (false, true) => change(path.into(), ChangeKind::StrictAdded),
+    | (true, false) => change(path.into(), ChangeKind::StrictRemoved),
+    | _ => {},
+  }
+  match (&old.rest, &new.rest) {
+    | (None, Some(_)) => change(path.into(), ChangeKind::RestAdded),
+    | (Some(_), None) => change(path.into(), ChangeKind::RestRemoved),
+    | _ => {},
+  }
+
+  for old_field in &old.fields {
+    match new.field(&old_field.name) {
+      | None => change(
+        field_path(old_field),
+        ChangeKind::FieldRemoved { optional: old_field.is_optional() },
+      ),
+      | Some(new_field) => {
+        match (old_field.is_optional(), new_field.is_optional()) {
+          | (false, true) => {
+            change(field_path(old_field), ChangeKind::FieldMadeOptional)
+          },
+          | (true, false) => {
+            change(field_path(old_field), ChangeKind::FieldMadeRequired)
+          },
+          | _ => {},
+        }
+      },
+    }
+  }
+  for new_field in &new.fields {
+    if old.field(&new_field.name).is_none() {
+      change(
+        field_path(new_field),
+        ChangeKind::FieldAdded { optional: new_field.is_optional() },
+      );
+    }
+  }
+
+  for old_field in &old.fields {
+    if let Some(new_field) = new.field(&old_field.name) {
+      diff_path(
+        &old_field.validator,
+        &new_field.validator,
+        &field_path(old_field),
+        changes,
+      );
+    }
+  }
+}
+
+fn diff_union(
+  old: &[Validator],
+  new: &[Validator],
+  path: &str,
+  changes: &mut Vec<SchemaChange>,
+) {
+  let label = |types: &[Validator], i: usize| {
+    types[i].branch_label().unwrap_or_else(|| (i + 1).to_string())
+  };
+
+  // Branches are matched when they are equal, or by their discriminant.
+  let mut matched = vec![false; new.len()];
+  for (i, old_branch) in old.iter().enumerate() {
+    let found = new
+      .iter()
+      .enumerate()
+      .filter(|(j, _)| !matched[*j])
+      .find(|(_, new_branch)| *new_branch == old_branch)
+      .or_else(|| {
+        let old_label = old_branch.branch_label()?;
+        new.iter().enumerate().filter(|(j, _)| !matched[*j]).find(
+          |(_, new_branch)| {
+            new_branch.branch_label().as_ref() == Some(&old_label)
+          },
+        )
+      });
+    match found {
+      | Some((j, new_branch)) => {
+        matched[j] = true;
+        let branch_path = format!("{}<{}>", path, label(old, i));
+        diff_path(old_branch, new_branch, &branch_path, changes);
+      },
+      | None => changes.push(SchemaChange {
+        path: path.into(),
+        kind: ChangeKind::UnionBranchRemoved { branch: label(old, i) },
+      }),
+    }
+  }
+  for (j, _) in new.iter().enumerate().filter(|(j, _)| !matched[*j]) {
+    changes.push(SchemaChange {
+      path: path.into(),
+      kind: ChangeKind::UnionBranchAdded { branch: label(new, j) },
+    });
+  }
+}
+
+impl Validator {
+  /// A short name for the kind of value this validator accepts.
+  pub fn kind_name(&self) -> &'static str {
+    match self {
+      | Validator::Id(_) => "id",
+      | Validator::Null => "null",
+      | Validator::Int64 => "int64",
+      | Validator::Number => "number",
+      | Validator::Boolean => "boolean",
+      | Validator::String => "string",
+      | Validator::Literal(_) => "literal",
+      | Validator::Array(_) => "array",
+      | Validator::Record(..) => "record",
+      | Validator::Object(_) => "object",
+      | Validator::Union(_) | Validator::Nullable(_) => "union",
+      | Validator::Optional(child) => child.kind_name(),
+    }
+  }
+
+  /// Names a union branch by its literal or first string literal field.
+  fn branch_label(&self) -> Option<String> {
+    match self {
+      | Validator::Literal(literal) => Some(literal.to_string()),
+      | Validator::Object(object) => {
+        object.fields.iter().find_map(|field| match &field.validator {
+          | Validator::Literal(Literal::String(s)) => Some(s.clone()),
+          | _ => None,
+        })
+      },
+      | _ => None,
+    }
+  }
+}
+
+impl ChangeKind {
+  pub fn compatibility(&self) -> Compatibility {
+    match self {
+      | ChangeKind::FieldAdded { optional: true } => Compatibility::Safe,
+      | ChangeKind::FieldAdded { optional: false } => {
+        Compatibility::BreaksNewReaders
+      },
+      | ChangeKind::FieldRemoved { optional: true } => Compatibility::Safe,
+      | ChangeKind::FieldRemoved { optional: false } => {
+        Compatibility::BreaksOldReaders
+      },
+      | ChangeKind::FieldMadeOptional => Compatibility::BreaksOldReaders,
+      | ChangeKind::FieldMadeRequired => Compatibility::BreaksNewReaders,
+      | ChangeKind::TypeChanged { .. }
+      | ChangeKind::LiteralChanged { .. }
+      | ChangeKind::IdTableChanged { .. } => Compatibility::Breaking,
+      | ChangeKind::UnionBranchAdded { .. } => Compatibility::BreaksOldReaders,
+      | ChangeKind::UnionBranchRemoved { .. } => {
+        Compatibility::BreaksNewReaders
+      },
+      | ChangeKind::StrictAdded => Compatibility::BreaksNewReaders,
+      | ChangeKind::StrictRemoved => Compatibility::BreaksOldReaders,
+      | ChangeKind::RestAdded | ChangeKind::RestRemoved => Compatibility::Safe,
+    }
+  }
+
+  /// A stable name for reports, like `field_added`.
+  pub fn name(&self) -> &'static str {
+    match self {
+      | ChangeKind::FieldAdded { .. } => "field_added",
+      | ChangeKind::FieldRemoved { .. } => "field_removed",
+      | ChangeKind::FieldMadeOptional => "field_made_optional",
+      | ChangeKind::FieldMadeRequired => "field_made_required",
+      | ChangeKind::TypeChanged { .. } => "type_changed",
+      | ChangeKind::LiteralChanged { .. } => "literal_changed",
+      | ChangeKind::IdTableChanged { .. } => "id_table_changed",
+      | ChangeKind::UnionBranchAdded { .. } => "union_branch_added",
+      | ChangeKind::UnionBranchRemoved { .. } => "union_branch_removed",
+      | ChangeKind::StrictAdded => "strict_added",
+      | ChangeKind::StrictRemoved => "strict_removed",
+      | ChangeKind::RestAdded => "rest_added",
+      | ChangeKind::RestRemoved => "rest_removed",
+    }
+  }
+}
+
+impl Compatibility {
+  pub fn name(&self) -> &'static str {
+    match self {
+      | Compatibility::Safe => "safe",
+      | Compatibility::BreaksOldReaders => "breaks_old_readers",
+      | Compatibility::BreaksNewReaders => "breaks_new_readers",
+      | Compatibility::Breaking => "breaking",
+    }
+  }
+}
+
+impl SchemaChange {
+  pub fn compatibility(&self) -> Compatibility {
+    self.kind.compatibility()
+  }
+
+  /// A machine-readable report of this change.
+  pub fn to_json(&self) -> Value {
+    let mut report = json!({
+      "path": self.path,
+      "change": self.kind.name(),
+      "compatibility": self.compatibility().name(),
+    });
+    match &self.kind {
+      | ChangeKind::FieldAdded { optional }
+      | ChangeKind::FieldRemoved { optional } => {
+        report["optional"] = Value::Bool(*optional);
+      },
+      | ChangeKind::TypeChanged { old, new }
+      | ChangeKind::IdTableChanged { old, new } => {
+        report["old"] = Value::from(old.as_str());
+        report["new"] = Value::from(new.as_str());
+      },
+      | ChangeKind::LiteralChanged { old, new } => {
+        report["old"] = Value::from(old.to_string());
+        report["new"] = Value::from(new.to_string());
+      },
+      | ChangeKind::UnionBranchAdded { branch }
+      | ChangeKind::UnionBranchRemoved { branch } => {
+        report["branch"] = Value::from(branch.as_str());
+      },
+      | _ => {},
+    }
+    report
+  }
+}
+
+impl fmt::Display for SchemaChange {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let path = if self.path.is_empty() { "(root)" } else { &self.path };
+    let description = match &self.kind {
+      | ChangeKind::FieldAdded { optional: true } => {
+        "optional field added".into()
+      },
+      | ChangeKind::FieldAdded { optional: false } => {
+        "required field added".into()
+      },
+      | ChangeKind::FieldRemoved { optional: true } => {
+        "optional field removed".into()
+      },
+      | ChangeKind::FieldRemoved { optional: false } => {
+        "required field removed".into()
+      },
+      | ChangeKind::FieldMadeOptional => "field made optional".into(),
+      | ChangeKind::FieldMadeRequired => "field made required".into(),
+      | ChangeKind::TypeChanged { old, new } => {
+        format!("type changed from {} to {}", old, new)
+      },
+      | ChangeKind::LiteralChanged { old, new } => {
+        format!("literal changed from {} to {}", old, new)
+      },
+      | ChangeKind::IdTableChanged { old, new } => {
+        format!("id table changed from {} to {}", old, new)
+      },
+      | ChangeKind::UnionBranchAdded { branch } => {
+        format!("union branch {} added", branch)
+      },
+      | ChangeKind::UnionBranchRemoved { branch } => {
+        format!("union branch {} removed", branch)
+      },
+      | ChangeKind::StrictAdded => "made strict".into(),
+      | ChangeKind::StrictRemoved => "no longer strict".into(),
+      | ChangeKind::RestAdded => "rest field added".into(),
+      | ChangeKind::RestRemoved => "rest field removed".into(),
+    };
+    write!(f, "{}: {} ({})", path, description, self.compatibility().name())
+  }
+}
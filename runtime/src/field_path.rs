@@ -0,0 +1,56 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use convex::Value;
+
+/// A dotted path to a field of a model, like `platform.username`, along with
+/// the type of its value.
+///
+/// Generated models have one constant per field path, so index and filter
+/// arguments are checked against the field's type.
+pub struct FieldPath<T> {
+  path: &'static str,
+  to_value: fn(&T) -> Value,
+  _t: PhantomData<fn() -> T>,
+}
+
+impl<T> FieldPath<T> {
+  pub const fn new(path: &'static str, to_value: fn(&T) -> Value) -> Self {
+    FieldPath { path, to_value, _t: PhantomData }
+  }
+
+  pub fn path(&self) -> &'static str {
+    self.path
+  }
+
+  /// Converts a value of this field into a `convex::Value`.
+  pub fn value(&self, value: &T) -> Value {
+    (self.to_value)(value)
+  }
+
+  /// The path and value, e.g. to build a `q.eq(path, value)` filter.
+  pub fn eq(&self, value: &T) -> (&'static str, Value) {
+    (self.path, self.value(value))
+  }
+}
+
+impl<T> Clone for FieldPath<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for FieldPath<T> {
+}
+
+impl<T> fmt::Debug for FieldPath<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("FieldPath").field(&self.path).finish()
+  }
+}
+
+impl<T> fmt::Display for FieldPath<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.path)
+  }
+}
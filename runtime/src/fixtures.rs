@@ -0,0 +1,85 @@
+/// Deterministic sample values for generated `fixture` functions.
+///
+/// The same seed always produces the same values, so fixtures can be used in
+/// snapshots and to seed local stand-ins for a deployment.
+pub struct Fixtures {
+  state: u64,
+}
+
+const NAMES: &[&str] = &[
+  "Alice", "Bob", "Carol", "Dave", "Erin", "Frank", "Grace", "Heidi", "Ivan",
+  "Judy", "Mallory", "Niaj", "Olivia", "Peggy", "Rupert", "Sybil", "Trent",
+  "Victor", "Walter", "Zoe",
+];
+
+const WORDS: &[&str] = &[
+  "apple", "breeze", "canyon", "delta", "ember", "forest", "glacier", "harbor",
+  "island", "jungle", "lagoon", "meadow", "nebula", "orchard", "prairie",
+  "quartz", "river", "summit", "tundra", "valley",
+];
+
+impl Fixtures {
+  pub fn new(seed: u64) -> Self {
+    Fixtures { state: seed }
+  }
+
+  /// The next pseudo random number, using splitmix64.
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+  }
+
+  /// Picks an index below `n`, `n` must not be zero.
+  pub fn pick(&mut self, n: usize) -> usize {
+    (self.next_u64() % n as u64) as usize
+  }
+
+  /// A string that fits a field named `field`, like a name for `name` or an
+  /// address for `email`.
+  pub fn string(&mut self, field: &str) -> String {
+    let field = field.to_ascii_lowercase();
+    let name = NAMES[self.pick(NAMES.len())];
+    if field.contains("email") {
+      format!("{}@example.com", name.to_ascii_lowercase())
+    } else if field.contains("url") {
+      format!("https://example.com/{}", WORDS[self.pick(WORDS.len())])
+    } else if field.contains("name") {
+      name.into()
+    } else {
+      WORDS[self.pick(WORDS.len())].into()
+    }
+  }
+
+  /// A string that looks like an id, ids are not checked against tables.
+  pub fn id(&mut self) -> String {
+    const CHARS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    (0..32).map(|_| CHARS[self.pick(CHARS.len())] as char).collect()
+  }
+
+  /// A small positive int.
+  pub fn int64(&mut self) -> i64 {
+    1 + self.pick(100) as i64
+  }
+
+  /// A small positive number with two decimals.
+  pub fn number(&mut self) -> f64 {
+    (1 + self.pick(10_000)) as f64 / 100.0
+  }
+
+  pub fn boolean(&mut self) -> bool {
+    self.pick(2) == 1
+  }
+
+  /// Whether an optional field is set.
+  pub fn some(&mut self) -> bool {
+    self.pick(4) != 0
+  }
+
+  /// The length of an array.
+  pub fn array_len(&mut self) -> usize {
+    1 + self.pick(3)
+  }
+}
@@ -0,0 +1,110 @@
+use crate::{Literal, Validator};
+
+impl Validator {
+  /// A stable fingerprint of this validator, the same one generated models
+  /// expose as `SCHEMA_HASH`.
+  ///
+  /// Object field order and the `#[strict]` and `..rest` markers don't affect
+  /// the hash, so it can be compared with one computed from `schema.ts`.
+  pub fn schema_hash(&self) -> u64 {
+    let mut hasher = Fnv1a::new();
+    self.hash_into(&mut hasher);
+    hasher.finish()
+  }
+
+  fn hash_into(&self, hasher: &mut Fnv1a) {
+    match self {
+      | Validator::Id(table) => {
+        hasher.write_str("id");
+        hasher.write_str(table);
+      },
+      | Validator::Null => hasher.write_str("null"),
+      | Validator::Int64 => hasher.write_str("int64"),
+      | Validator::Number => hasher.write_str("number"),
+      | Validator::Boolean => hasher.write_str("boolean"),
+      | Validator::String => hasher.write_str("string"),
+      | Validator::Literal(literal) => {
+        hasher.write_str("literal");
+        match literal {
+          | Literal::String(s) => {
+            hasher.write_str("string");
+            hasher.write_str(s);
+          },
+          | Literal::Boolean(b) => {
+            hasher.write_str("boolean");
+            hasher.write(&[*b as u8]);
+          },
+          | Literal::Int64(i) => {
+            hasher.write_str("int64");
+            hasher.write(&i.to_le_bytes());
+          },
+          | Literal::Float64(n) => {
+            hasher.write_str("float64");
+            hasher.write(&n.to_bits().to_le_bytes());
+          },
+        }
+      },
+      | Validator::Array(child) => {
+        hasher.write_str("array");
+        child.hash_into(hasher);
+      },
+      | Validator::Record(keys, values) => {
+        hasher.write_str("record");
+        keys.hash_into(hasher);
+        values.hash_into(hasher);
+      },
+      | Validator::Optional(child) => {
+        hasher.write_str("optional");
+        child.hash_into(hasher);
+      },
+      // Hashed like the union Convex expands it to.
+      | Validator::Nullable(child) => {
+        Validator::Union(vec![(**child).clone(), Validator::Null])
+          .hash_into(hasher);
+      },
+      | Validator::Union(types) => {
+        hasher.write_str("union");
+        hasher.write(&(types.len() as u64).to_le_bytes());
+        for t in types {
+          t.hash_into(hasher);
+        }
+      },
+      | Validator::Object(object) => {
+        hasher.write_str("object");
+        hasher.write(&(object.fields.len() as u64).to_le_bytes());
+        let mut fields: Vec<_> = object.fields.iter().collect();
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        for field in fields {
+          hasher.write_str(&field.name);
+          field.validator.hash_into(hasher);
+        }
+      },
+    }
+  }
+}
+
+// FNV-1a, unlike `DefaultHasher` it is stable across Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+  fn new() -> Self {
+    Fnv1a(0xCBF29CE484222325)
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    for byte in bytes {
+      self.0 ^= *byte as u64;
+      self.0 = self.0.wrapping_mul(0x100000001B3);
+    }
+  }
+
+  // Length prefixed so "ab" + "c" and "a" + "bc" differ.
+  fn write_str(&mut self, s: &str) {
+    self.write(&(s.len() as u64).to_le_bytes());
+    self.write(s.as_bytes());
+  }
+
+  fn finish(&self) -> u64 {
+    self.0
+  }
+}
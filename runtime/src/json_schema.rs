@@ -0,0 +1,120 @@
+use serde_json::{json, Map, Value};
+
+use crate::{Literal, Validator};
+
+impl Validator {
+  /// Converts this validator into a JSON Schema (draft 2020-12) describing the
+  /// JSON generated models serialize to.
+  ///
+  /// Ids are strings with an `x-convex-table` annotation. Unions of objects
+  /// sharing a string literal field are a `oneOf` with a `discriminator` hint,
+  /// other unions are an `anyOf` since the first matching branch is used.
+  pub fn to_json_schema(&self) -> Value {
+    match self {
+      | Validator::Id(table) => json!({
+        "type": "string",
+        "x-convex-table": table,
+      }),
+      | Validator::Null => json!({ "type": "null" }),
+      | Validator::Int64 => json!({ "type": "integer" }),
+      // Special floats use Convex's `{"$float": ...}` encoding.
+      | Validator::Number => json!({
+        "anyOf": [
+          { "type": "number" },
+          {
+            "type": "object",
+            "properties": { "$float": { "type": "string" } },
+            "required": ["$float"],
+            "additionalProperties": false,
+          },
+        ],
+      }),
+      | Validator::Boolean => json!({ "type": "boolean" }),
+      | Validator::String => json!({ "type": "string" }),
+      | Validator::Literal(literal) => match literal {
+        | Literal::String(s) => json!({ "const": s }),
+        | Literal::Boolean(b) => json!({ "const": b }),
+        | Literal::Int64(i) => json!({ "const": i }),
+        | Literal::Float64(n) => json!({ "const": n }),
+      },
+      | Validator::Array(child) => json!({
+        "type": "array",
+        "items": child.to_json_schema(),
+      }),
+      | Validator::Record(keys, values) => json!({
+        "type": "object",
+        "propertyNames": keys.to_json_schema(),
+        "additionalProperties": values.to_json_schema(),
+      }),
+      | Validator::Nullable(child) => json!({
+        "anyOf": [child.to_json_schema(), { "type": "null" }],
+      }),
+      // Optional fields are left out of their object's `required` list.
+      | Validator::Optional(child) => child.to_json_schema(),
+      | Validator::Object(object) => {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in &object.fields {
+          properties
+            .insert(field.name.clone(), field.validator.to_json_schema());
+          if !field.is_optional() {
+            required.push(Value::String(field.name.clone()));
+          }
+        }
+        let mut schema = json!({
+          "type": "object",
+          "properties": properties,
+          "required": required,
+        });
+        if object.strict {
+          schema["additionalProperties"] = Value::Bool(false);
+        }
+        schema
+      },
+      | Validator::Union(types) => {
+        let branches: Vec<Value> =
+          types.iter().map(|t| t.to_json_schema()).collect();
+        match self.discriminant() {
+          | Some(property) => json!({
+            "oneOf": branches,
+            "discriminator": { "propertyName": property },
+          }),
+          | None => json!({ "anyOf": branches }),
+        }
+      },
+    }
+  }
+
+  /// The string literal field every branch of an object union shares.
+  fn discriminant(&self) -> Option<&str> {
+    let Validator::Union(types) = self else {
+      return None;
+    };
+    let first = types.first()?.fields()?;
+    first
+      .iter()
+      .filter(|field| {
+        matches!(field.validator, Validator::Literal(Literal::String(_)))
+      })
+      .map(|field| field.name.as_str())
+      .find(|name| {
+        let mut values = Vec::new();
+        let all_literals = types.iter().all(|t| {
+          let literal = t
+            .fields()
+            .and_then(|fields| fields.iter().find(|field| field.name == *name));
+          match literal.map(|field| &field.validator) {
+            | Some(Validator::Literal(Literal::String(value))) => {
+              values.push(value);
+              true
+            },
+            | _ => false,
+          }
+        });
+        let count = values.len();
+        values.sort();
+        values.dedup();
+        all_literals && values.len() == count
+      })
+  }
+}
@@ -0,0 +1,43 @@
+//! Runtime support for code generated by
+//! [`ragkit_convex_macros`](https://docs.rs/ragkit_convex_macros).
+
+#[cfg(feature = "convex")]
+pub use client::{into_value, FunctionClient, FunctionError};
+pub use deserialize::deserialize_some;
+#[cfg(feature = "convex")]
+pub use deserialize::{
+  deserialize_float,
+  deserialize_rest,
+  deserialize_some_float,
+  FloatField,
+};
+pub use diff::{diff, ChangeKind, Compatibility, SchemaChange};
+#[cfg(feature = "convex")]
+pub use field_path::FieldPath;
+pub use fixtures::Fixtures;
+#[cfg(feature = "parse")]
+pub use parse::ParseError;
+#[cfg(feature = "convex")]
+pub use subscription::Subscription;
+#[cfg(feature = "convex")]
+pub use validate::{ValidationError, ValidationErrors};
+pub use validator::{FieldValidator, Literal, ObjectValidator, Validator};
+
+#[cfg(feature = "convex")]
+mod client;
+mod deserialize;
+mod diff;
+#[cfg(feature = "convex")]
+mod field_path;
+mod fixtures;
+mod hash;
+mod json_schema;
+#[cfg(feature = "parse")]
+pub mod parse;
+#[cfg(feature = "convex")]
+mod subscription;
+#[cfg(feature = "parse")]
+mod tokens;
+#[cfg(feature = "convex")]
+mod validate;
+mod validator;
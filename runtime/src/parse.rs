@@ -0,0 +1,342 @@
+//! The validator grammar shared by the macros and [`Validator::parse`].
+//!
+//! These functions parse from a [`ParseStream`], so inside a macro errors
+//! point at the offending tokens.
+
+use std::fmt;
+
+use syn::parse::{Parse, ParseBuffer, ParseStream, Parser};
+use syn::{Attribute, Error, Ident, Lit, Meta, Result, Token};
+
+use crate::{FieldValidator, Literal, ObjectValidator, Validator};
+
+/// An error parsing validator source, with its position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+  pub message: String,
+  /// 1-indexed line of the error.
+  pub line: usize,
+  /// 1-indexed column of the error.
+  pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at {}:{}", self.message, self.line, self.column)
+  }
+}
+
+impl std::error::Error for ParseError {
+}
+
+impl From<Error> for ParseError {
+  fn from(error: Error) -> Self {
+    let start = error.span().start();
+    ParseError {
+      message: error.to_string(),
+      line: start.line,
+      column: start.column + 1,
+    }
+  }
+}
+
+impl Validator {
+  /// Parses validator source like `v.object({ name: v.string() })` using the
+  /// same grammar as the macros.
+  pub fn parse(source: &str) -> std::result::Result<Validator, ParseError> {
+    parse_validator.parse_str(source).map_err(ParseError::from)
+  }
+}
+
+/// Parses a validator call like `v.string()` or `#[strict] v.object({ ... })`.
+pub fn parse_validator(input: ParseStream) -> Result<Validator> {
+  // #[strict] v.object(...)
+  // ^^^^^^^^^
+  let strict_span = input.span();
+  let strict = parse_strict_attribute(input)?;
+
+  // v.string(...)
+  // ^
+  let v = Ident::parse(input)?;
+  if v != "v" {
+    return Err(Error::new_spanned(&v, "Expected v.method()"));
+  }
+
+  // v.string(...)
+  //  ^
+  let _ = input.parse::<Token![.]>()?;
+
+  // v.string(...)
+  //   ^^^^^^
+  let method_ident = Ident::parse(input)?;
+  let method = method_ident.to_string();
+
+  // v.string(...)
+  //         ^^^^^
+  let inner;
+  let _ = syn::parenthesized!(inner in input);
+
+  if strict && method != "object" {
+    return Err(Error::new(
+      strict_span,
+      "#[strict] only applies to v.object()",
+    ));
+  }
+
+  match method.as_str() {
+    | "id" => {
+      let lit = Lit::parse(&inner)?;
+      match lit.clone() {
+        | Lit::Str(str_lit) => Ok(Validator::Id(str_lit.value())),
+        | _ => Err(Error::new_spanned(&lit, "Expected string literal")),
+      }
+    },
+    | "null" => Ok(Validator::Null),
+    // `v.bigint()` is a deprecated alias of `v.int64()`.
+    | "int64" | "bigint" => Ok(Validator::Int64),
+    | "number" | "float64" => Ok(Validator::Number),
+    | "boolean" => Ok(Validator::Boolean),
+    | "string" => Ok(Validator::String),
+
+    | "literal" => {
+      // v.literal(-1)
+      //           ^
+      let negative = inner.parse::<Option<Token![-]>>()?.is_some();
+      let lit = Lit::parse(&inner)?;
+      let literal = match lit.clone() {
+        | Lit::Str(s) if !negative => Literal::String(s.value()),
+        | Lit::Bool(b) if !negative => Literal::Boolean(b.value()),
+        // Like TypeScript, only bigint literals like `10n` are int64.
+        | Lit::Int(i) if i.suffix() == "n" => {
+          let digits = i.base10_digits();
+          let digits =
+            if negative { format!("-{}", digits) } else { digits.into() };
+          let value = digits.parse::<i64>().map_err(|_| {
+            Error::new_spanned(&lit, "Bigint literals must fit in an int64")
+          })?;
+          Literal::Int64(value)
+        },
+        | Lit::Int(i) if i.suffix().is_empty() => {
+          float_literal(&lit, i.base10_parse::<f64>()?, negative)?
+        },
+        | Lit::Float(f) if f.suffix().is_empty() => {
+          float_literal(&lit, f.base10_parse::<f64>()?, negative)?
+        },
+        | _ => return Err(Error::new_spanned(&lit, "Unsupported literal")),
+      };
+      Ok(Validator::Literal(literal))
+    },
+
+    | "optional" => {
+      let child = parse_validator(&inner)?;
+      Ok(Validator::Optional(Box::new(child)))
+    },
+
+    | "nullable" => {
+      let child = parse_validator(&inner)?;
+      if let Validator::Optional(_) = child {
+        return Err(Error::new_spanned(
+          &method_ident,
+          "Nullable values may not be optional, use \
+           v.optional(v.nullable(...))",
+        ));
+      }
+      Ok(Validator::Nullable(Box::new(child)))
+    },
+
+    | "array" => {
+      let child = parse_validator(&inner)?;
+      if let Validator::Optional(_) = child {
+        return Err(Error::new_spanned(
+          &method_ident,
+          "Arrays may not contain optional values",
+        ));
+      }
+      Ok(Validator::Array(Box::new(child)))
+    },
+
+    | "record" => {
+      let types = parse_comma_separated(&inner, parse_validator)?;
+      let [keys, values]: [Validator; 2] = types.try_into().map_err(|_| {
+        Error::new_spanned(&method_ident, "Records must have keys and values")
+      })?;
+      if !matches!(keys, Validator::String | Validator::Id(_)) {
+        return Err(Error::new_spanned(
+          &method_ident,
+          "Record keys must be v.string() or v.id()",
+        ));
+      }
+      if let Validator::Optional(_) = values {
+        return Err(Error::new_spanned(
+          &method_ident,
+          "Records may not contain optional values",
+        ));
+      }
+      Ok(Validator::Record(Box::new(keys), Box::new(values)))
+    },
+
+    | "object" => {
+      let object_inner;
+      let _ = syn::braced!(object_inner in inner);
+      let object = parse_object(&object_inner, strict)?;
+      Ok(Validator::Object(object))
+    },
+
+    | "union" => {
+      let types = parse_comma_separated(&inner, parse_validator)?;
+      if types.len() < 2 {
+        return Err(Error::new_spanned(
+          &method_ident,
+          "Unions must have 2 or more branches",
+        ));
+      }
+      for t in &types {
+        let message = match t {
+          | Validator::Nullable(_) => {
+            "Unions may not contain nullable values, add v.null() to the union \
+             instead"
+          },
+          | Validator::Optional(_) => {
+            "Unions may not contain optional branches"
+          },
+          | Validator::Union(_) => {
+            "Unions may not directly contain other unions, put other types \
+             between them"
+          },
+          | Validator::Array(child) | Validator::Record(_, child)
+            if has_struct(child) =>
+          {
+            "Unions may not contain arrays or records of objects or unions"
+          },
+          | _ => continue,
+        };
+        return Err(Error::new_spanned(&method_ident, message));
+      }
+      Ok(Validator::Union(types))
+    },
+
+    | _ => Err(Error::new_spanned(&method_ident, "Unsupported validator call")),
+  }
+}
+
+/// Whether generated code holds `validator` in a struct or enum of its own.
+fn has_struct(validator: &Validator) -> bool {
+  match validator {
+    | Validator::Object(_) | Validator::Union(_) => true,
+    | Validator::Optional(child)
+    | Validator::Nullable(child)
+    | Validator::Array(child)
+    | Validator::Record(_, child) => has_struct(child),
+    | _ => false,
+  }
+}
+
+/// Number literals without an `n` suffix are float64, like in TypeScript.
+fn float_literal(lit: &Lit, value: f64, negative: bool) -> Result<Literal> {
+  if !value.is_finite() {
+    return Err(Error::new_spanned(lit, "Number literals must be finite"));
+  }
+  Ok(Literal::Float64(if negative { -value } else { value }))
+}
+
+// Items inside an object body, `..rest` is only allowed last.
+enum ObjectItem {
+  Field(FieldValidator),
+  Rest(Ident),
+}
+
+/// Parses the fields inside the braces of `v.object({ ... })`.
+pub fn parse_object(
+  buffer: &ParseBuffer,
+  strict: bool,
+) -> Result<ObjectValidator> {
+  let items = parse_comma_separated(buffer, |b| {
+    if b.peek(Token![..]) {
+      // ..rest
+      let _ = b.parse::<Token![..]>()?;
+      Ok(ObjectItem::Rest(Ident::parse(b)?))
+    } else {
+      Ok(ObjectItem::Field(parse_field(b)?))
+    }
+  })?;
+
+  let mut fields: Vec<FieldValidator> = Vec::new();
+  let mut rest: Option<Ident> = None;
+  for item in items {
+    if let Some(rest) = &rest {
+      return Err(Error::new_spanned(rest, "Rest field must be last"));
+    }
+    match item {
+      | ObjectItem::Field(field) => fields.push(field),
+      | ObjectItem::Rest(ident) => {
+        if fields.iter().any(|field| ident == field.name) {
+          return Err(Error::new_spanned(&ident, "Duplicate field name"));
+        }
+        if strict {
+          return Err(Error::new_spanned(
+            &ident,
+            "#[strict] objects may not have a rest field",
+          ));
+        }
+        rest = Some(ident);
+      },
+    }
+  }
+
+  Ok(ObjectValidator {
+    fields,
+    strict,
+    rest: rest.map(|ident| ident.to_string()),
+  })
+}
+
+/// Parses the attributes before a validator, returning whether `#[strict]` was
+/// one of them.
+pub fn parse_strict_attribute(input: ParseStream) -> Result<bool> {
+  let mut strict = false;
+  for attribute in input.call(Attribute::parse_outer)? {
+    match &attribute.meta {
+      | Meta::Path(path) if path.is_ident("strict") => strict = true,
+      | _ => {
+        return Err(Error::new_spanned(&attribute, "Unsupported attribute"));
+      },
+    }
+  }
+  Ok(strict)
+}
+
+fn parse_field(input: ParseStream) -> Result<FieldValidator> {
+  // name: v.string(...)
+  // ^^^^
+  let ident = Ident::parse(input)?;
+
+  // name: v.string(...)
+  //     ^
+  let _ = input.parse::<Token![:]>()?;
+
+  // name: v.string(...)
+  //       ^^^^^^^^^^^^^
+  let validator = parse_validator(input)?;
+
+  Ok(FieldValidator { name: ident.to_string(), validator })
+}
+
+fn parse_comma_separated<T>(
+  buffer: &ParseBuffer,
+  f: fn(&ParseBuffer) -> Result<T>,
+) -> Result<Vec<T>> {
+  let mut results = Vec::new();
+  let mut first = true;
+  let mut comma_token = Ok(());
+  while !buffer.is_empty() {
+    if !first {
+      // Must have comma token if this wasn't the first item.
+      comma_token?;
+    }
+    let x = f(buffer)?;
+    results.push(x);
+    comma_token = buffer.parse::<Token![,]>().map(|_| ());
+    first = false;
+  }
+  Ok(results)
+}
@@ -0,0 +1,51 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use convex::{FunctionResult, Value};
+use futures_core::Stream;
+
+use crate::{into_value, FunctionError};
+
+/// Decodes each update of a stream of [`FunctionResult`]s, such as a
+/// `convex::QuerySubscription`.
+///
+/// Every item is decoded once. Error results and values that fail to decode
+/// are yielded as errors and the subscription keeps going.
+pub struct Subscription<S, T> {
+  stream: S,
+  decode: fn(&Value) -> anyhow::Result<T>,
+}
+
+impl<S, T> Subscription<S, T> {
+  pub fn new(stream: S, decode: fn(&Value) -> anyhow::Result<T>) -> Self {
+    Self { stream, decode }
+  }
+
+  /// Returns the underlying stream, e.g. to unsubscribe by dropping it.
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S, T> Stream for Subscription<S, T>
+where S: Stream<Item = FunctionResult> + Unpin
+{
+  type Item = Result<T, FunctionError>;
+
+  fn poll_next(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    let decode = self.decode;
+    Pin::new(&mut self.stream).poll_next(cx).map(|result| {
+      result.map(|result| {
+        let value = into_value(result)?;
+        decode(&value).map_err(FunctionError::Decode)
+      })
+    })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.stream.size_hint()
+  }
+}
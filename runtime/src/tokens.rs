@@ -0,0 +1,90 @@
+//! Prints validators as Rust expressions building them, so the macros can
+//! embed the validator a model was generated from.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::validator::{FieldValidator, Literal, ObjectValidator, Validator};
+
+impl ToTokens for Validator {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let validator = quote! { ::ragkit_convex_runtime::Validator };
+    tokens.extend(match self {
+      | Validator::Id(table) => {
+        quote! { #validator::Id(::std::string::String::from(#table)) }
+      },
+      | Validator::Null => quote! { #validator::Null },
+      | Validator::Int64 => quote! { #validator::Int64 },
+      | Validator::Number => quote! { #validator::Number },
+      | Validator::Boolean => quote! { #validator::Boolean },
+      | Validator::String => quote! { #validator::String },
+      | Validator::Literal(literal) => quote! { #validator::Literal(#literal) },
+      | Validator::Array(child) => {
+        quote! { #validator::Array(::std::boxed::Box::new(#child)) }
+      },
+      | Validator::Record(keys, values) => quote! {
+        #validator::Record(
+          ::std::boxed::Box::new(#keys),
+          ::std::boxed::Box::new(#values),
+        )
+      },
+      | Validator::Object(object) => quote! { #validator::Object(#object) },
+      | Validator::Union(types) => {
+        quote! { #validator::Union(::std::vec![#( #types ),*]) }
+      },
+      | Validator::Nullable(child) => {
+        quote! { #validator::Nullable(::std::boxed::Box::new(#child)) }
+      },
+      | Validator::Optional(child) => {
+        quote! { #validator::Optional(::std::boxed::Box::new(#child)) }
+      },
+    });
+  }
+}
+
+impl ToTokens for Literal {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let literal = quote! { ::ragkit_convex_runtime::Literal };
+    tokens.extend(match self {
+      | Literal::String(s) => {
+        quote! { #literal::String(::std::string::String::from(#s)) }
+      },
+      | Literal::Boolean(b) => quote! { #literal::Boolean(#b) },
+      | Literal::Int64(i) => quote! { #literal::Int64(#i) },
+      | Literal::Float64(n) => quote! { #literal::Float64(#n) },
+    });
+  }
+}
+
+impl ToTokens for ObjectValidator {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let fields = &self.fields;
+    let strict = self.strict;
+    let rest = match &self.rest {
+      | Some(rest) => quote! {
+        ::core::option::Option::Some(::std::string::String::from(#rest))
+      },
+      | None => quote! { ::core::option::Option::None },
+    };
+    tokens.extend(quote! {
+      ::ragkit_convex_runtime::ObjectValidator {
+        fields: ::std::vec![#( #fields ),*],
+        strict: #strict,
+        rest: #rest,
+      }
+    });
+  }
+}
+
+impl ToTokens for FieldValidator {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let name = &self.name;
+    let validator = &self.validator;
+    tokens.extend(quote! {
+      ::ragkit_convex_runtime::FieldValidator {
+        name: ::std::string::String::from(#name),
+        validator: #validator,
+      }
+    });
+  }
+}
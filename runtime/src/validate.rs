@@ -0,0 +1,168 @@
+use std::fmt;
+
+use convex::Value;
+
+use crate::{Literal, Validator};
+
+/// A value that did not match a validator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+  /// Where the error is, like `platform.username` or `tags[2]`. Empty for the
+  /// value itself.
+  pub path: String,
+  pub message: String,
+}
+
+/// Every [`ValidationError`] found while validating a value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.path.is_empty() {
+      write!(f, "{}", self.message)
+    } else {
+      write!(f, "{}: {}", self.path, self.message)
+    }
+  }
+}
+
+impl fmt::Display for ValidationErrors {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let errors: Vec<String> =
+      self.0.iter().map(|error| error.to_string()).collect();
+    write!(f, "{}", errors.join(", "))
+  }
+}
+
+impl std::error::Error for ValidationError {
+}
+
+impl std::error::Error for ValidationErrors {
+}
+
+impl Validator {
+  /// Checks `value` against this validator without decoding it, accepting
+  /// exactly the values a generated `from_convex_value` accepts.
+  ///
+  /// Unlike generated models every error is reported, not just the first.
+  pub fn validate(&self, value: &Value) -> Result<(), ValidationErrors> {
+    let mut errors = Vec::new();
+    self.validate_path(value, "", &mut errors);
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(ValidationErrors(errors))
+    }
+  }
+
+  fn validate_path(
+    &self,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+  ) {
+    let mut error = |message: String| {
+      errors.push(ValidationError { path: path.into(), message })
+    };
+    match (self, value) {
+      | (Validator::Id(_) | Validator::String, Value::String(_)) => {},
+      | (Validator::Id(_) | Validator::String, _) => {
+        error("Expected a string".into())
+      },
+      | (Validator::Null, Value::Null) => {},
+      | (Validator::Null, _) => error("Expected null".into()),
+      // Ints and floats are coerced into each other.
+      | (Validator::Int64 | Validator::Number, Value::Int64(_))
+      | (Validator::Int64 | Validator::Number, Value::Float64(_)) => {},
+      | (Validator::Int64, _) => error("Expected an int".into()),
+      | (Validator::Number, _) => error("Expected a number".into()),
+      | (Validator::Boolean, Value::Boolean(_)) => {},
+      | (Validator::Boolean, _) => error("Expected a boolean".into()),
+      | (Validator::Literal(literal), value) => {
+        if !literal.matches(value) {
+          error(format!("Expected the literal {}", literal))
+        }
+      },
+      | (Validator::Array(child), Value::Array(values)) => {
+        for (i, value) in values.iter().enumerate() {
+          child.validate_path(value, &format!("{}[{}]", path, i), errors);
+        }
+      },
+      | (Validator::Array(_), _) => error("Expected an array".into()),
+      // Keys are strings by construction, ids aren't checked like values.
+      | (Validator::Record(_, child), Value::Object(fields)) => {
+        for (key, value) in fields {
+          let key_path = if path.is_empty() {
+            key.clone()
+          } else {
+            format!("{}.{}", path, key)
+          };
+          child.validate_path(value, &key_path, errors);
+        }
+      },
+      | (Validator::Record(..), _) => error("Expected an object".into()),
+      | (Validator::Object(object), Value::Object(fields)) => {
+        if object.strict {
+          let unknown_fields: Vec<&str> = fields
+            .keys()
+            .map(|key| key.as_str())
+            .filter(|key| object.field(key).is_none())
+            .collect();
+          if !unknown_fields.is_empty() {
+            error(format!("Unexpected fields: {}", unknown_fields.join(", ")));
+          }
+        }
+        for field in &object.fields {
+          let field_path = if path.is_empty() {
+            field.name.clone()
+          } else {
+            format!("{}.{}", path, field.name)
+          };
+          match (&field.validator, fields.get(&field.name)) {
+            | (Validator::Optional(_), None) => {},
+            | (_, None) => errors.push(ValidationError {
+              path: field_path,
+              message: "Missing required field".into(),
+            }),
+            | (validator, Some(value)) => {
+              validator.validate_path(value, &field_path, errors)
+            },
+          }
+        }
+      },
+      | (Validator::Object(_), _) => error("Expected an object".into()),
+      // The first matching branch is used, so any match is enough.
+      | (Validator::Union(types), value) => {
+        if !types.iter().any(|t| t.validate(value).is_ok()) {
+          error("Expected a value matching the union".into())
+        }
+      },
+      | (Validator::Nullable(_), Value::Null) => {},
+      | (Validator::Nullable(child), value) => {
+        child.validate_path(value, path, errors)
+      },
+      | (Validator::Optional(child), value) => {
+        child.validate_path(value, path, errors)
+      },
+    }
+  }
+}
+
+impl Literal {
+  fn matches(&self, value: &Value) -> bool {
+    match (self, value) {
+      | (Literal::String(literal), Value::String(value)) => literal == value,
+      | (Literal::Boolean(literal), Value::Boolean(value)) => literal == value,
+      | (Literal::Int64(literal), Value::Int64(value)) => literal == value,
+      | (Literal::Int64(literal), Value::Float64(value)) => {
+        value.fract() == 0.0 && *literal == *value as i64
+      },
+      | (Literal::Float64(literal), Value::Float64(value)) => literal == value,
+      | (Literal::Float64(literal), Value::Int64(value)) => {
+        *literal == *value as f64
+      },
+      | _ => false,
+    }
+  }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// A Convex validator, describing the values a generated type accepts.
+///
+/// This mirrors the validator syntax used by the macros, so generic code can
+/// walk any generated model through its `convex_schema()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Validator {
+  /// `v.id("table")`, holding the table name.
+  Id(String),
+  Null,
+  Int64,
+  Number,
+  Boolean,
+  String,
+  Literal(Literal),
+  Array(Box<Validator>),
+  /// `v.record(keys, values)`, keys are `v.string()` or `v.id("table")`.
+  Record(Box<Validator>, Box<Validator>),
+  Object(ObjectValidator),
+  Union(Vec<Validator>),
+  /// `v.nullable(x)`, the same as `v.union(x, v.null())`.
+  Nullable(Box<Validator>),
+  /// Only valid as the validator of an object field.
+  Optional(Box<Validator>),
+}
+
+/// The value of a `v.literal(...)` validator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+  String(String),
+  Boolean(bool),
+  Int64(i64),
+  /// Always finite, compared exactly.
+  Float64(f64),
+}
+
+/// A `v.object({ ... })` validator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectValidator {
+  pub fields: Vec<FieldValidator>,
+  /// Rejects fields that are not declared, see `#[strict]`.
+  pub strict: bool,
+  /// The name of the `..rest` field collecting undeclared fields.
+  pub rest: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldValidator {
+  pub name: String,
+  pub validator: Validator,
+}
+
+impl Validator {
+  /// The fields of an object validator.
+  pub fn fields(&self) -> Option<&[FieldValidator]> {
+    match self {
+      | Validator::Object(object) => Some(&object.fields),
+      | _ => None,
+    }
+  }
+}
+
+impl ObjectValidator {
+  pub fn field(&self, name: &str) -> Option<&FieldValidator> {
+    self.fields.iter().find(|field| field.name == name)
+  }
+}
+
+impl FieldValidator {
+  pub fn is_optional(&self) -> bool {
+    matches!(self.validator, Validator::Optional(_))
+  }
+}
+
+impl fmt::Display for Literal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      | Literal::String(s) => write!(f, "{:?}", s),
+      | Literal::Boolean(b) => write!(f, "{}", b),
+      // Written like TypeScript, where only bigint literals are int64.
+      | Literal::Int64(i) => write!(f, "{}n", i),
+      | Literal::Float64(n) => write!(f, "{}", n),
+    }
+  }
+}
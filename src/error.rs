@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::Result;
+
+use crate::model::{ConvexField, ConvexType};
+
+/// A model describing the `data` of a thrown `ConvexError`.
+pub struct ConvexErrorModel {
+  pub model: ConvexField,
+}
+
+impl Parse for ConvexErrorModel {
+  fn parse(input: ParseStream) -> Result<Self> {
+    // AppError { ... }
+    let model = ConvexField::parse(input)?;
+    Ok(Self { model })
+  }
+}
+
+impl ConvexErrorModel {
+  pub fn print(&self) -> Vec<TokenStream> {
+    let struct_name = self.model.name.to_struct_name();
+    let mut items = self.model.print();
+
+    // Prefer a `message: v.string()` field, otherwise print the data as JSON.
+    let has_message = match &self.model.t {
+      | ConvexType::Object(object) => object.fields.iter().any(|field| {
+        field.name.id == "message" && matches!(field.t, ConvexType::String)
+      }),
+      | _ => false,
+    };
+    let display = if has_message {
+      quote! { write!(f, "{}", self.message) }
+    } else {
+      quote! {
+        match ::serde_json::to_string(self) {
+          | ::core::result::Result::Ok(json) => write!(f, "{}", json),
+          | ::core::result::Result::Err(_) => write!(f, "{:?}", self),
+        }
+      }
+    };
+
+    items.push(quote! {
+      impl ::core::convert::TryFrom<&::convex::ConvexError> for #struct_name {
+        type Error = ::anyhow::Error;
+
+        fn try_from(
+          error: &::convex::ConvexError,
+        ) -> ::core::result::Result<Self, Self::Error> {
+          Self::from_convex_value(&error.data)
+        }
+      }
+
+      impl ::core::fmt::Display for #struct_name {
+        fn fmt(
+          &self,
+          f: &mut ::core::fmt::Formatter<'_>,
+        ) -> ::core::fmt::Result {
+          #display
+        }
+      }
+
+      impl ::std::error::Error for #struct_name {}
+    });
+    items
+  }
+}
@@ -0,0 +1,235 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Ident, LitStr, Result, Token};
+
+use crate::model::{
+  capitalize_first_char,
+  ConvexField,
+  ConvexName,
+  ConvexObject,
+  ConvexType,
+};
+
+pub struct ConvexFunction {
+  pub kind: Ident,
+  pub name: Ident,
+  pub path: String,
+  pub args: ConvexField,
+  // Functions without a returns validator give back the raw value.
+  pub returns: Option<ConvexField>,
+}
+
+impl Parse for ConvexFunction {
+  fn parse(input: ParseStream) -> Result<Self> {
+    // query Name "messages:list" { ... }
+    // ^^^^^
+    let kind = Ident::parse(input)?;
+    if !["query", "mutation", "action"].contains(&kind.to_string().as_str()) {
+      return Err(Error::new_spanned(
+        &kind,
+        "Expected query, mutation or action",
+      ));
+    }
+
+    // query Name "messages:list" { ... }
+    //       ^^^^
+    let explicit_name =
+      if input.peek(Ident) { Some(Ident::parse(input)?) } else { None };
+
+    // query Name "messages:list" { ... }
+    //            ^^^^^^^^^^^^^^^
+    let path_lit = input.parse::<LitStr>()?;
+    let path = path_lit.value();
+    let name = match explicit_name {
+      | Some(name) => name,
+      | None => Self::path_to_name(&path).ok_or_else(|| {
+        Error::new_spanned(&path_lit, "Add a name like `query Name \"path\"`")
+      })?,
+    };
+    let function_name = ConvexName { path: Vec::new(), id: name.to_string() };
+
+    // query Name "messages:list" { ... }
+    //                            ^^^^^^^
+    let content;
+    let _ = syn::braced!(content in input);
+    let mut args = None;
+    let mut returns = None;
+    while !content.is_empty() {
+      let key = Ident::parse(&content)?;
+      let _ = content.parse::<Token![:]>()?;
+      let field_name =
+        ConvexName { path: function_name.full_path(), id: key.to_string() };
+      match key.to_string().as_str() {
+        | "args" if args.is_none() => {
+          let args_inner;
+          let _ = syn::braced!(args_inner in content);
+          let object =
+            ConvexField::parse_object(&field_name, &args_inner, false)?;
+          args = Some(ConvexField {
+            name: field_name,
+            t: ConvexType::Object(object),
+          });
+        },
+        | "returns" if returns.is_none() => {
+          let t = ConvexField::parse_validator_call(&field_name, &content)?;
+          returns = Some(ConvexField { name: field_name, t });
+        },
+        | _ => {
+          return Err(Error::new_spanned(&key, "Expected args or returns"));
+        },
+      }
+      if !content.is_empty() {
+        let _ = content.parse::<Token![,]>()?;
+      }
+    }
+
+    // Functions without args take an empty object.
+    let args = args.unwrap_or_else(|| ConvexField {
+      name: ConvexName { path: function_name.full_path(), id: "args".into() },
+      t: ConvexType::Object(ConvexObject {
+        fields: Vec::new(),
+        strict: false,
+        rest: None,
+      }),
+    });
+
+    Ok(Self { kind, name, path, args, returns })
+  }
+}
+
+impl ConvexFunction {
+  pub fn print(&self) -> Vec<TokenStream> {
+    let name = &self.name;
+    let path = &self.path;
+    let kind = self.kind.to_string();
+    let args_name = self.args.name.to_struct_name();
+    let mut items = self.args.print();
+    items.push(quote! {
+      impl #args_name {
+        pub fn to_convex_args(
+          &self
+        ) -> ::std::collections::BTreeMap<String, ::convex::Value> {
+          match self.to_convex_value() {
+            | ::convex::Value::Object(object) => object,
+            | _ => unreachable!("Objects always convert to Value::Object"),
+          }
+        }
+      }
+    });
+
+    let returns_type = match &self.returns {
+      | Some(returns) => returns.print_type(),
+      | None => quote! { ::convex::Value },
+    };
+    let returns_fn = match &self.returns {
+      | Some(returns) => {
+        items.append(&mut returns.print_nested());
+        let returns_ident = returns.name.to_field_name();
+        let target = Ident::new("value", Span::call_site());
+        let extract = ConvexField::print_extract_field(returns, Some(target));
+        quote! {
+          pub fn returns_from_convex_value(
+            value: &::convex::Value
+          ) -> ::core::result::Result<#returns_type, ::anyhow::Error> {
+            let value = ::core::option::Option::Some(value);
+            #extract
+            ::core::result::Result::Ok(#returns_ident)
+          }
+        }
+      },
+      | None => quote! {
+        pub fn returns_from_convex_value(
+          value: &::convex::Value
+        ) -> ::core::result::Result<::convex::Value, ::anyhow::Error> {
+          ::core::result::Result::Ok(value.clone())
+        }
+      },
+    };
+
+    let call_fn = if cfg!(feature = "client") {
+      let kind = &self.kind;
+      quote! {
+        pub async fn call<C>(
+          client: &mut C,
+          args: #args_name,
+        ) -> ::core::result::Result<#returns_type, ::ragkit_convex_runtime::FunctionError>
+        where C: ::ragkit_convex_runtime::FunctionClient {
+          let result = ::ragkit_convex_runtime::FunctionClient::#kind(
+            client,
+            Self::PATH,
+            args.to_convex_args(),
+          )
+          .await
+          .map_err(::ragkit_convex_runtime::FunctionError::Client)?;
+          let value = ::ragkit_convex_runtime::into_value(result)?;
+          Self::returns_from_convex_value(&value)
+            .map_err(::ragkit_convex_runtime::FunctionError::Decode)
+        }
+      }
+    } else {
+      quote! {}
+    };
+
+    // Only queries can be subscribed to.
+    let subscribe_fns = if cfg!(feature = "client") && kind == "query" {
+      quote! {
+        /// Decodes every update of a stream of results for this query.
+        pub fn subscription<S>(
+          stream: S,
+        ) -> ::ragkit_convex_runtime::Subscription<S, #returns_type> {
+          ::ragkit_convex_runtime::Subscription::new(
+            stream,
+            Self::returns_from_convex_value,
+          )
+        }
+
+        pub async fn subscribe(
+          client: &mut ::convex::ConvexClient,
+          args: #args_name,
+        ) -> ::core::result::Result<
+          ::ragkit_convex_runtime::Subscription<
+            ::convex::QuerySubscription,
+            #returns_type,
+          >,
+          ::ragkit_convex_runtime::FunctionError,
+        > {
+          let stream = client
+            .subscribe(Self::PATH, args.to_convex_args())
+            .await
+            .map_err(::ragkit_convex_runtime::FunctionError::Client)?;
+          ::core::result::Result::Ok(Self::subscription(stream))
+        }
+      }
+    } else {
+      quote! {}
+    };
+
+    items.push(quote! {
+      #[derive(Clone, Copy, Debug)]
+      pub struct #name;
+    });
+    items.push(quote! {
+      impl #name {
+        pub const PATH: &'static str = #path;
+        pub const KIND: &'static str = #kind;
+
+        #returns_fn
+
+        #call_fn
+
+        #subscribe_fns
+      }
+    });
+    items
+  }
+
+  /// Converts a path like `"folder/messages:list"` into `FolderMessagesList`.
+  fn path_to_name(path: &str) -> Option<Ident> {
+    let name: String = path
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .map(capitalize_first_char)
+      .collect();
+    syn::parse_str::<Ident>(&name).ok()
+  }
+}
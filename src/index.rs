@@ -0,0 +1,624 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Ident, LitInt, LitStr, Result, Token};
+
+use crate::model::{
+  capitalize_first_char,
+  ConvexField,
+  ConvexObject,
+  ConvexType,
+};
+
+/// An index declared after a model, like
+/// `.index("by_author", ["author", "createdAt"])`.
+pub struct ConvexIndex {
+  pub name: LitStr,
+  pub kind: ConvexIndexKind,
+}
+
+pub enum ConvexIndexKind {
+  Db {
+    fields: Vec<LitStr>,
+  },
+  // .searchIndex("search_body", { searchField: "body", filterFields: [...] })
+  Search {
+    search_field: LitStr,
+    filter_fields: Vec<LitStr>,
+  },
+  // .vectorIndex("by_embedding", { vectorField: "embedding", dimensions: 1536
+  // })
+  Vector {
+    vector_field: LitStr,
+    dimensions: LitInt,
+    filter_fields: Vec<LitStr>,
+  },
+}
+
+impl Parse for ConvexIndex {
+  fn parse(input: ParseStream) -> Result<Self> {
+    // .index("by_author", ["author", "createdAt"])
+    // ^
+    let _ = input.parse::<Token![.]>()?;
+
+    // .index("by_author", ["author", "createdAt"])
+    //  ^^^^^
+    let method = Ident::parse(input)?;
+
+    // .index("by_author", ["author", "createdAt"])
+    //        ^^^^^^^^^^^
+    let inner;
+    let _ = syn::parenthesized!(inner in input);
+    let name = inner.parse::<LitStr>()?;
+    let _ = inner.parse::<Token![,]>()?;
+
+    let kind = match method.to_string().as_str() {
+      // .index("by_author", ["author", "createdAt"])
+      //                     ^^^^^^^^^^^^^^^^^^^^^^^
+      | "index" => {
+        let fields = parse_field_list(&inner)?;
+        if fields.is_empty() {
+          return Err(Error::new_spanned(
+            &name,
+            "Indexes need at least one field",
+          ));
+        }
+        ConvexIndexKind::Db { fields }
+      },
+      // .searchIndex("search_body", { searchField: "body", ... })
+      //                             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      | "searchIndex" => {
+        let mut search_field = None;
+        let mut filter_fields = None;
+        parse_options(&inner, |key, input| {
+          match key.to_string().as_str() {
+            | "searchField" if search_field.is_none() => {
+              search_field = Some(input.parse::<LitStr>()?);
+            },
+            | "filterFields" if filter_fields.is_none() => {
+              filter_fields = Some(parse_field_list(input)?);
+            },
+            | _ => {
+              return Err(Error::new_spanned(
+                key,
+                "Expected searchField or filterFields",
+              ));
+            },
+          }
+          Ok(())
+        })?;
+        let search_field = search_field.ok_or_else(|| {
+          Error::new_spanned(&name, "Search indexes need a searchField")
+        })?;
+        ConvexIndexKind::Search {
+          search_field,
+          filter_fields: filter_fields.unwrap_or_default(),
+        }
+      },
+      // .vectorIndex("by_embedding", { vectorField: "embedding", ... })
+      //                              ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      | "vectorIndex" => {
+        let mut vector_field = None;
+        let mut dimensions = None;
+        let mut filter_fields = None;
+        parse_options(&inner, |key, input| {
+          match key.to_string().as_str() {
+            | "vectorField" if vector_field.is_none() => {
+              vector_field = Some(input.parse::<LitStr>()?);
+            },
+            | "dimensions" if dimensions.is_none() => {
+              let lit = input.parse::<LitInt>()?;
+              if lit.base10_parse::<usize>()? == 0 {
+                return Err(Error::new_spanned(
+                  &lit,
+                  "Dimensions must be positive",
+                ));
+              }
+              dimensions = Some(lit);
+            },
+            | "filterFields" if filter_fields.is_none() => {
+              filter_fields = Some(parse_field_list(input)?);
+            },
+            | _ => {
+              return Err(Error::new_spanned(
+                key,
+                "Expected vectorField, dimensions or filterFields",
+              ));
+            },
+          }
+          Ok(())
+        })?;
+        let vector_field = vector_field.ok_or_else(|| {
+          Error::new_spanned(&name, "Vector indexes need a vectorField")
+        })?;
+        let dimensions = dimensions.ok_or_else(|| {
+          Error::new_spanned(&name, "Vector indexes need dimensions")
+        })?;
+        ConvexIndexKind::Vector {
+          vector_field,
+          dimensions,
+          filter_fields: filter_fields.unwrap_or_default(),
+        }
+      },
+      | _ => {
+        return Err(Error::new_spanned(
+          &method,
+          "Expected .index(), .searchIndex() or .vectorIndex()",
+        ));
+      },
+    };
+    if !inner.is_empty() {
+      let _ = inner.parse::<Token![,]>()?;
+    }
+
+    Ok(Self { name, kind })
+  }
+}
+
+/// Parses `["author", "meta.channel"]`.
+fn parse_field_list(input: ParseStream) -> Result<Vec<LitStr>> {
+  let inner;
+  let _ = syn::bracketed!(inner in input);
+  Ok(
+    inner
+      .parse_terminated(|input| input.parse::<LitStr>(), Token![,])?
+      .into_iter()
+      .collect(),
+  )
+}
+
+/// Parses `{ key: value, ... }`, leaving each value to `parse_value`.
+fn parse_options(
+  input: ParseStream,
+  mut parse_value: impl FnMut(&Ident, ParseStream) -> Result<()>,
+) -> Result<()> {
+  let inner;
+  let _ = syn::braced!(inner in input);
+  while !inner.is_empty() {
+    let key = Ident::parse(&inner)?;
+    let _ = inner.parse::<Token![:]>()?;
+    parse_value(&key, &inner)?;
+    if !inner.is_empty() {
+      let _ = inner.parse::<Token![,]>()?;
+    }
+  }
+  Ok(())
+}
+
+impl ConvexIndex {
+  /// Checks every indexed field exists in `object` with a type the index
+  /// supports, returning the field holding each one's value. Search and
+  /// vector indexes list their search or vector field first.
+  pub fn resolve_fields<'a>(
+    &self,
+    object: &'a ConvexObject,
+  ) -> Result<Vec<(String, &'a ConvexField)>> {
+    let paths = object.field_paths();
+    let resolve = |field: &LitStr| {
+      let path = field.value();
+      paths
+        .iter()
+        .find(|(p, _)| p.join(".") == path)
+        .map(|(_, f)| (path.clone(), *f))
+        .ok_or_else(|| {
+          Error::new_spanned(field, format!("Unknown field '{}'", path))
+        })
+    };
+    match &self.kind {
+      | ConvexIndexKind::Db { fields } => {
+        check_struct_fields(fields, None)?;
+        fields.iter().map(resolve).collect()
+      },
+      | ConvexIndexKind::Search { search_field, filter_fields } => {
+        let resolved = resolve(search_field)?;
+        if !matches!(resolved.1.t, ConvexType::String) {
+          return Err(Error::new_spanned(
+            search_field,
+            format!("Search field '{}' must be a v.string()", resolved.0),
+          ));
+        }
+        check_struct_fields(filter_fields, Some("query"))?;
+        let mut fields = vec![resolved];
+        for field in filter_fields {
+          fields.push(resolve(field)?);
+        }
+        Ok(fields)
+      },
+      | ConvexIndexKind::Vector { vector_field, filter_fields, .. } => {
+        let resolved = resolve(vector_field)?;
+        let is_vector = match &resolved.1.t {
+          | ConvexType::Array(child) => matches!(child.t, ConvexType::Number),
+          | _ => false,
+        };
+        if !is_vector {
+          return Err(Error::new_spanned(
+            vector_field,
+            format!(
+              "Vector field '{}' must be a v.array(v.number())",
+              resolved.0
+            ),
+          ));
+        }
+        check_struct_fields(filter_fields, Some("vector"))?;
+        let mut fields = vec![resolved];
+        for field in filter_fields {
+          fields.push(resolve(field)?);
+        }
+        Ok(fields)
+      },
+    }
+  }
+
+  /// The struct generated for this index of the model named `struct_name`,
+  /// like `MessageByAuthor` for `"by_author"`.
+  pub fn struct_name(&self, struct_name: &Ident) -> Ident {
+    Ident::new(
+      format!("{}{}", struct_name, to_struct_suffix(&self.name.value()))
+        .as_str(),
+      Span::call_site(),
+    )
+  }
+
+  /// Prints a struct holding the typed key or arguments of this index for
+  /// the model named `struct_name`.
+  pub fn print(
+    &self,
+    struct_name: &Ident,
+    fields: &[(String, &ConvexField)],
+  ) -> TokenStream {
+    let index_name = self.name.value();
+    let index_struct = self.struct_name(struct_name);
+    match &self.kind {
+      | ConvexIndexKind::Db { .. } => {
+        Self::print_db(&index_struct, &index_name, fields)
+      },
+      | ConvexIndexKind::Search { .. } => {
+        Self::print_search(&index_struct, &index_name, fields)
+      },
+      | ConvexIndexKind::Vector { dimensions, .. } => {
+        Self::print_vector(&index_struct, &index_name, dimensions, fields)
+      },
+    }
+  }
+
+  fn print_db(
+    index_struct: &Ident,
+    index_name: &str,
+    fields: &[(String, &ConvexField)],
+  ) -> TokenStream {
+    let paths = fields.iter().map(|(path, _)| path);
+    let field_idents: Vec<Ident> =
+      fields.iter().map(|(path, _)| to_field_ident(path)).collect();
+    let field_types: Vec<TokenStream> =
+      fields.iter().map(|(_, field)| field.print_type()).collect();
+    let inserts =
+      fields.iter().zip(&field_idents).map(|((path, field), ident)| {
+        let value = field.t.print_to_convex_value(quote! { &self.#ident });
+        quote! { args.insert(::std::string::String::from(#path), #value); }
+      });
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[derive(Clone, Debug, PartialEq)]
+      pub struct #index_struct {
+        #( pub #field_idents: #field_types, )*
+      }
+
+      #[allow(non_snake_case)]
+      impl #index_struct {
+        pub const NAME: &'static str = #index_name;
+        pub const FIELDS: &'static [&'static str] = &[#( #paths ),*];
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(#( #field_idents: #field_types ),*) -> Self {
+          #index_struct { #( #field_idents ),* }
+        }
+
+        /// The key as arguments keyed by field path.
+        pub fn to_convex_args(
+          &self
+        ) -> ::std::collections::BTreeMap<String, ::convex::Value> {
+          let mut args = ::std::collections::BTreeMap::new();
+          #( #inserts )*
+          args
+        }
+      }
+    }
+  }
+
+  fn print_search(
+    index_struct: &Ident,
+    index_name: &str,
+    fields: &[(String, &ConvexField)],
+  ) -> TokenStream {
+    let (search_path, _) = &fields[0];
+    let filters = &fields[1..];
+    let filter_paths = filters.iter().map(|(path, _)| path);
+    let filter_fields = Self::print_filter_fields(filters);
+    let filter_inserts = Self::print_filter_inserts(filters);
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[derive(Clone, Debug, Default, PartialEq)]
+      pub struct #index_struct {
+        pub query: String,
+        #filter_fields
+      }
+
+      #[allow(non_snake_case)]
+      impl #index_struct {
+        pub const NAME: &'static str = #index_name;
+        pub const SEARCH_FIELD: &'static str = #search_path;
+        pub const FILTER_FIELDS: &'static [&'static str] =
+          &[#( #filter_paths ),*];
+
+        /// Searches for `query` without any filters, set them on the
+        /// returned value.
+        pub fn new(query: String) -> Self {
+          #index_struct { query, ..::core::default::Default::default() }
+        }
+
+        /// The search query and filters keyed by field path.
+        pub fn to_convex_args(
+          &self
+        ) -> ::std::collections::BTreeMap<String, ::convex::Value> {
+          let mut args = ::std::collections::BTreeMap::new();
+          args.insert(
+            ::std::string::String::from(#search_path),
+            ::convex::Value::String(self.query.clone()),
+          );
+          #filter_inserts
+          args
+        }
+      }
+    }
+  }
+
+  fn print_vector(
+    index_struct: &Ident,
+    index_name: &str,
+    dimensions: &LitInt,
+    fields: &[(String, &ConvexField)],
+  ) -> TokenStream {
+    let (vector_path, _) = &fields[0];
+    let filters = &fields[1..];
+    let filter_paths = filters.iter().map(|(path, _)| path);
+    let filter_fields = Self::print_filter_fields(filters);
+    let filter_inserts = Self::print_filter_inserts(filters);
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[derive(Clone, Debug, Default, PartialEq)]
+      pub struct #index_struct {
+        pub vector: Vec<f64>,
+        #filter_fields
+      }
+
+      #[allow(non_snake_case)]
+      impl #index_struct {
+        pub const NAME: &'static str = #index_name;
+        pub const VECTOR_FIELD: &'static str = #vector_path;
+        pub const DIMENSIONS: usize = #dimensions;
+        pub const FILTER_FIELDS: &'static [&'static str] =
+          &[#( #filter_paths ),*];
+
+        /// Searches near `vector` without any filters, set them on the
+        /// returned value. Fails when `vector` doesn't have `DIMENSIONS`
+        /// values.
+        pub fn new(
+          vector: Vec<f64>
+        ) -> ::core::result::Result<Self, ::anyhow::Error> {
+          if vector.len() != Self::DIMENSIONS {
+            return ::core::result::Result::Err(::anyhow::anyhow!(
+              "Expected {} dimensions for vector index '{}', got {}",
+              Self::DIMENSIONS,
+              Self::NAME,
+              vector.len(),
+            ));
+          }
+          ::core::result::Result::Ok(#index_struct {
+            vector,
+            ..::core::default::Default::default()
+          })
+        }
+
+        /// The vector and filters keyed by field path.
+        pub fn to_convex_args(
+          &self
+        ) -> ::std::collections::BTreeMap<String, ::convex::Value> {
+          let mut args = ::std::collections::BTreeMap::new();
+          args.insert(
+            ::std::string::String::from(#vector_path),
+            ::convex::Value::Array(
+              self.vector.iter().map(|v| ::convex::Value::Float64(*v)).collect(),
+            ),
+          );
+          #filter_inserts
+          args
+        }
+      }
+    }
+  }
+
+  /// Filters are optional, only the ones that are set are applied.
+  fn print_filter_fields(filters: &[(String, &ConvexField)]) -> TokenStream {
+    let fields = filters.iter().map(|(path, field)| {
+      let ident = to_field_ident(path);
+      let field_type = field.print_type();
+      quote! { pub #ident: Option<#field_type>, }
+    });
+    quote! { #( #fields )* }
+  }
+
+  fn print_filter_inserts(filters: &[(String, &ConvexField)]) -> TokenStream {
+    let inserts = filters.iter().map(|(path, field)| {
+      let ident = to_field_ident(path);
+      let value = field.t.print_to_convex_value(quote! { value });
+      quote! {
+        if let ::core::option::Option::Some(value) = &self.#ident {
+          args.insert(::std::string::String::from(#path), #value);
+        }
+      }
+    });
+    quote! { #( #inserts )* }
+  }
+}
+
+/// Checks `fields` become distinct struct fields, none of them named like the
+/// index struct's own `builtin` field.
+fn check_struct_fields(fields: &[LitStr], builtin: Option<&str>) -> Result<()> {
+  for (i, field) in fields.iter().enumerate() {
+    let path = field.value();
+    let ident = to_field_ident(&path).to_string();
+    if builtin == Some(ident.as_str()) {
+      return Err(Error::new_spanned(
+        field,
+        format!(
+          "Filter field '{}' conflicts with the built-in `{}` field",
+          path, ident
+        ),
+      ));
+    }
+    let duplicate =
+      fields[..i].iter().find(|other| to_field_ident(&other.value()) == ident);
+    if let Some(other) = duplicate {
+      return Err(Error::new_spanned(
+        field,
+        format!(
+          "Fields '{}' and '{}' both become the field `{}`",
+          other.value(),
+          path,
+          ident
+        ),
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Converts a path like `"meta.channel"` into `meta_channel`.
+fn to_field_ident(path: &str) -> Ident {
+  Ident::new(&path.replace('.', "_"), Span::call_site())
+}
+
+/// Converts a name like `"by_author"` into `ByAuthor`.
+fn to_struct_suffix(name: &str) -> String {
+  name
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .map(capitalize_first_char)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use proc_macro2::TokenStream;
+  use quote::quote;
+
+  use crate::model::ConvexModel;
+
+  fn parse_error(tokens: TokenStream) -> String {
+    match syn::parse2::<ConvexModel>(tokens) {
+      | Ok(_) => panic!("Expected the model to be rejected"),
+      | Err(error) => error.to_string(),
+    }
+  }
+
+  #[test]
+  fn unknown_fields() {
+    assert_eq!(
+      "Unknown field 'author'",
+      parse_error(quote! {
+        Message { body: v.string() }.index("by_author", ["author"])
+      }),
+    );
+    assert_eq!(
+      "Unknown field 'meta.tag'",
+      parse_error(quote! {
+        Message { meta: v.object({ kind: v.string() }) }
+          .index("by_tag", ["meta.tag"])
+      }),
+    );
+  }
+
+  #[test]
+  fn duplicate_index_names() {
+    assert_eq!(
+      "Duplicate index name 'by_a'",
+      parse_error(quote! {
+        Doc { a: v.string() }.index("by_a", ["a"]).index("by_a", ["a"])
+      }),
+    );
+  }
+
+  #[test]
+  fn index_struct_collisions() {
+    assert_eq!(
+      "Index 'by_author' generates `MessageByAuthor`, which is already \
+       generated for this model",
+      parse_error(quote! {
+        Message { byAuthor: v.object({ name: v.string() }) }
+          .index("by_author", ["byAuthor.name"])
+      }),
+    );
+    assert_eq!(
+      "Index 'byA' generates `DocByA`, which is already generated for this \
+       model",
+      parse_error(quote! {
+        Doc { a: v.string() }.index("by_a", ["a"]).index("byA", ["a"])
+      }),
+    );
+  }
+
+  #[test]
+  fn search_and_vector_field_types() {
+    assert_eq!(
+      "Search field 'views' must be a v.string()",
+      parse_error(quote! {
+        Doc { views: v.int64() }
+          .searchIndex("search_views", { searchField: "views" })
+      }),
+    );
+    assert_eq!(
+      "Vector field 'embedding' must be a v.array(v.number())",
+      parse_error(quote! {
+        Doc { embedding: v.array(v.int64()) }
+          .vectorIndex("by_embedding", { vectorField: "embedding", dimensions: 3 })
+      }),
+    );
+    assert_eq!(
+      "Dimensions must be positive",
+      parse_error(quote! {
+        Doc { embedding: v.array(v.number()) }
+          .vectorIndex("by_embedding", { vectorField: "embedding", dimensions: 0 })
+      }),
+    );
+  }
+
+  #[test]
+  fn filter_fields_with_builtin_names() {
+    assert_eq!(
+      "Filter field 'query' conflicts with the built-in `query` field",
+      parse_error(quote! {
+        Doc { body: v.string(), query: v.string() }
+          .searchIndex("search_body", { searchField: "body", filterFields: ["query"] })
+      }),
+    );
+    assert_eq!(
+      "Filter field 'vector' conflicts with the built-in `vector` field",
+      parse_error(quote! {
+        Doc { embedding: v.array(v.number()), vector: v.string() }
+          .vectorIndex("by_embedding", {
+            vectorField: "embedding",
+            dimensions: 3,
+            filterFields: ["vector"],
+          })
+      }),
+    );
+    assert_eq!(
+      "Fields 'a' and 'a' both become the field `a`",
+      parse_error(quote! {
+        Doc { a: v.string() }.index("by_a", ["a", "a"])
+      }),
+    );
+  }
+}
@@ -1,9 +1,14 @@
 extern crate proc_macro;
 
-use model::ConvexField;
+use error::ConvexErrorModel;
+use function::ConvexFunction;
+use model::ConvexModel;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
+mod error;
+mod function;
+mod index;
 mod model;
 
 /// Create models using the same [Convex validator](https://docs.convex.dev/functions/args-validation#convex-values) syntax as your schema definition.
@@ -55,9 +60,138 @@ mod model;
 ///   json!(user),
 /// );
 /// ```
+///
+/// Unknown fields are ignored by default. Mark the model with `#[strict]` to
+/// reject them in every object, or mark a single `v.object(...)`.
+///
+/// ```ignore
+/// convex_model!(#[strict] User {
+///   name: v.string(),
+///   settings: v.object({ theme: v.string() }),
+/// });
+///
+/// convex_model!(Post {
+///   body: v.string(),
+///   author: #[strict] v.object({ name: v.string() }),
+/// });
+/// ```
+///
+/// End an object with `..name` to collect undeclared fields into a
+/// `BTreeMap<String, convex::Value>` that is written back out by
+/// `to_convex_value` and serialization.
+///
+/// ```ignore
+/// convex_model!(User {
+///   name: v.string(),
+///   ..rest
+/// });
+/// ```
+///
+/// Constructors take every required field and fill in literals, optional
+/// fields start as `None`. Union branches get constructors named after their
+/// string literal, falling back to `variant_N`.
+///
+/// ```ignore
+/// let user = User::new(
+///   "1234".into(),
+///   "Alice".into(),
+///   UserPlatform::github("alicecodes".into()),
+/// )
+/// .with_age(Some(42));
+/// ```
+///
+/// Each model also gets a `<Model>Patch` struct for `db.patch` style updates.
+/// `None` leaves a field untouched and `Some(None)` unsets an optional field.
+///
+/// ```ignore
+/// let patch = UserPatch { age: Some(None), ..Default::default() };
+/// let value = patch.to_convex_value();
+/// patch.apply(&mut user);
+/// ```
+///
+/// Every generated struct and enum has a `CONVEX_VALIDATOR_TS` constant with
+/// its TypeScript validator source, e.g. `v.object({ name: v.string() })`,
+/// and a `convex_schema()` function returning the same validator as a
+/// `ragkit_convex_runtime::Validator` for generic tooling. `json_schema()`
+/// returns a JSON Schema for its JSON, which also implements
+/// `schemars::JsonSchema` with the `schemars` feature.
+///
+/// Versioned models like `convex_model!(User v2 { ... })` generate `UserV2`
+/// with `UserV2::from_any_version`, which falls back to `UserV1` and upgrades
+/// it through a user provided `From<UserV1> for UserV2` impl.
 #[proc_macro]
 pub fn convex_model(input: TokenStream) -> TokenStream {
-  let input = parse_macro_input!(input as ConvexField);
+  let input = parse_macro_input!(input as ConvexModel);
+  let output = input.print();
+  let ts = proc_macro2::TokenStream::from_iter(output);
+  ts.into()
+}
+
+/// Create typed arguments and return values for a Convex function using the
+/// same validator syntax as the function definition.
+///
+/// ```ignore
+/// convex_function!(query "messages:list" {
+///   args: { channel: v.id("channels") },
+///   returns: v.array(v.object({
+///     author: v.string(),
+///     body: v.string(),
+///   })),
+/// });
+/// ```
+///
+/// This generates `pub struct MessagesList;` holding the function path in
+/// `MessagesList::PATH`, a `MessagesListArgs` model with `to_convex_args`, and
+/// `MessagesList::returns_from_convex_value` which decodes into
+/// `Vec<MessagesListReturns>`.
+///
+/// The name is derived from the function path, pass one explicitly with
+/// `query ListMessages "messages:list" { ... }`. Without `returns` the raw
+/// `convex::Value` is returned.
+///
+/// With the `client` feature, `MessagesList::call` runs the function through
+/// any `ragkit_convex_runtime::FunctionClient`, such as `convex::ConvexClient`.
+///
+/// ```ignore
+/// let messages = MessagesList::call(&mut client, args).await?;
+/// ```
+///
+/// Queries also get `subscribe`, and `subscription` to decode any stream of
+/// `convex::FunctionResult`s, yielding `Result<Vec<MessagesListReturns>, _>`
+/// per update.
+#[proc_macro]
+pub fn convex_function(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as ConvexFunction);
+  let output = input.print();
+  let ts = proc_macro2::TokenStream::from_iter(output);
+  ts.into()
+}
+
+/// Create a typed model for the `data` of a thrown `ConvexError`, using the
+/// same syntax as [`convex_model!`].
+///
+/// ```ignore
+/// convex_error!(AppError {
+///   code: v.union(v.literal("NOT_FOUND"), v.literal("FORBIDDEN")),
+///   message: v.string(),
+/// });
+/// ```
+///
+/// Along with the model this generates `TryFrom<&convex::ConvexError>` and
+/// `std::error::Error`, so thrown errors can be matched on.
+///
+/// ```ignore
+/// if let FunctionResult::ConvexError(error) = result {
+///   let error = AppError::try_from(&error)?;
+///   if matches!(error.code, AppErrorCode::Variant1(_)) { ... }
+/// }
+/// ```
+///
+/// Errors with a `message: v.string()` field display it, others display their
+/// data as JSON.
+#[proc_macro]
+pub fn convex_error(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as ConvexErrorModel);
   let output = input.print();
   let ts = proc_macro2::TokenStream::from_iter(output);
   ts.into()
@@ -1,7 +1,16 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use ragkit_convex_runtime::{
+  parse,
+  FieldValidator,
+  Literal,
+  ObjectValidator,
+  Validator,
+};
 use syn::parse::{Parse, ParseBuffer, ParseStream};
-use syn::{Error, Ident, Lit, Result, Token};
+use syn::{Error, Ident, Result, Token};
+
+use crate::index::ConvexIndex;
 
 #[derive(Clone)]
 pub struct ConvexName {
@@ -15,6 +24,15 @@ pub struct ConvexField {
   pub t: ConvexType,
 }
 
+#[derive(Clone)]
+pub struct ConvexObject {
+  pub fields: Vec<ConvexField>,
+  // Reject keys that are not declared in fields.
+  pub strict: bool,
+  // Name of the field collecting keys that are not declared in fields.
+  pub rest: Option<String>,
+}
+
 // See: https://docs.convex.dev/functions/args-validation
 #[derive(Clone)]
 pub enum ConvexType {
@@ -26,12 +44,17 @@ pub enum ConvexType {
   Bool,
   String,
   // TODO: Bytes,
-  // TODO: Array(ConvexType),
-  Object(Vec<ConvexField>),
+  Array(Box<ConvexField>),
+  // v.record(keys, values), keys are Id or String.
+  Record(Box<ConvexType>, Box<ConvexField>),
+  Object(ConvexObject),
   Union(Vec<ConvexType>),
+  // v.nullable(x), a required field that may be null.
+  Nullable(Box<ConvexField>),
   StringLiteral(String),
   BoolLiteral(bool),
   IntLiteral(i64),
+  FloatLiteral(f64),
   // TODO: Any,
   Optional(Box<ConvexField>),
 }
@@ -51,6 +74,7 @@ impl ConvexType {
       | ConvexType::StringLiteral(_) => Some(quote! { String }),
       | ConvexType::BoolLiteral(_) => Some(quote! { bool }),
       | ConvexType::IntLiteral(_) => Some(quote! { i64 }),
+      | ConvexType::FloatLiteral(_) => Some(quote! { f64 }),
 
       // Kinda a weird one, we technically know the full type even if the child
       // is an Object or Union, but other parts of the system rely on returning
@@ -58,16 +82,515 @@ impl ConvexType {
       | ConvexType::Optional(child) => {
         child.t.print().map(|ts| quote! { Option<#ts> })
       },
+      | ConvexType::Array(child) => {
+        child.t.print().map(|ts| quote! { Vec<#ts> })
+      },
+      | ConvexType::Record(_, child) => child
+        .t
+        .print()
+        .map(|ts| quote! { ::std::collections::BTreeMap<String, #ts> }),
+      | ConvexType::Nullable(child) => {
+        child.t.print().map(|ts| quote! { Option<#ts> })
+      },
 
       // These depend on field.name to generate a struct name.
       | ConvexType::Object(_) => None,
       | ConvexType::Union(_) => None,
     }
   }
+
+  /// Names the parsed `validator` for `name`, which determines the names of
+  /// the structs and enums it generates.
+  fn from_validator(name: &ConvexName, validator: Validator) -> ConvexType {
+    let child = |validator| {
+      Box::new(ConvexField {
+        name: name.clone(),
+        t: ConvexType::from_validator(name, validator),
+      })
+    };
+    match validator {
+      | Validator::Id(table) => ConvexType::Id(table),
+      | Validator::Null => ConvexType::Null,
+      | Validator::Int64 => ConvexType::Int64,
+      | Validator::Number => ConvexType::Number,
+      | Validator::Boolean => ConvexType::Bool,
+      | Validator::String => ConvexType::String,
+      | Validator::Literal(Literal::String(s)) => ConvexType::StringLiteral(s),
+      | Validator::Literal(Literal::Boolean(b)) => ConvexType::BoolLiteral(b),
+      | Validator::Literal(Literal::Int64(i)) => ConvexType::IntLiteral(i),
+      | Validator::Literal(Literal::Float64(n)) => ConvexType::FloatLiteral(n),
+      | Validator::Optional(validator) => {
+        ConvexType::Optional(child(*validator))
+      },
+      | Validator::Array(validator) => ConvexType::Array(child(*validator)),
+      | Validator::Record(keys, validator) => ConvexType::Record(
+        Box::new(ConvexType::from_validator(name, *keys)),
+        child(*validator),
+      ),
+      | Validator::Nullable(validator) => {
+        ConvexType::Nullable(child(*validator))
+      },
+      | Validator::Union(types) => ConvexType::Union(
+        types
+          .into_iter()
+          .map(|validator| ConvexType::from_validator(name, validator))
+          .collect(),
+      ),
+      | Validator::Object(object) => {
+        ConvexType::Object(ConvexObject::from_validator(name, object))
+      },
+    }
+  }
+
+  /// The inverse of `from_validator`.
+  fn to_validator(&self) -> Validator {
+    match &self {
+      | ConvexType::Id(table) => Validator::Id(table.clone()),
+      | ConvexType::Null => Validator::Null,
+      | ConvexType::Int64 => Validator::Int64,
+      | ConvexType::Number => Validator::Number,
+      | ConvexType::Bool => Validator::Boolean,
+      | ConvexType::String => Validator::String,
+      | ConvexType::StringLiteral(s) => {
+        Validator::Literal(Literal::String(s.clone()))
+      },
+      | ConvexType::BoolLiteral(b) => Validator::Literal(Literal::Boolean(*b)),
+      | ConvexType::IntLiteral(i) => Validator::Literal(Literal::Int64(*i)),
+      | ConvexType::FloatLiteral(n) => Validator::Literal(Literal::Float64(*n)),
+      | ConvexType::Optional(child) => {
+        Validator::Optional(Box::new(child.t.to_validator()))
+      },
+      | ConvexType::Array(child) => {
+        Validator::Array(Box::new(child.t.to_validator()))
+      },
+      | ConvexType::Record(keys, child) => Validator::Record(
+        Box::new(keys.to_validator()),
+        Box::new(child.t.to_validator()),
+      ),
+      | ConvexType::Nullable(child) => {
+        Validator::Nullable(Box::new(child.t.to_validator()))
+      },
+      | ConvexType::Union(types) => {
+        Validator::Union(types.iter().map(|t| t.to_validator()).collect())
+      },
+      | ConvexType::Object(object) => Validator::Object(ObjectValidator {
+        fields: object
+          .fields
+          .iter()
+          .map(|field| FieldValidator {
+            name: field.name.id.clone(),
+            validator: field.t.to_validator(),
+          })
+          .collect(),
+        strict: object.strict,
+        rest: object.rest.clone(),
+      }),
+    }
+  }
+
+  /// Prints a proptest strategy for simple types, objects and unions depend
+  /// on field.name like `print`.
+  fn print_strategy(&self) -> Option<TokenStream> {
+    let any = |t: TokenStream| quote! { ::proptest::arbitrary::any::<#t>() };
+    match &self {
+      // Ids look like real ids but aren't validated against a table.
+      | ConvexType::Id(_) => Some(quote! { "[0-9a-z]{32}" }),
+      | ConvexType::Null => Some(quote! { ::proptest::strategy::Just(()) }),
+      | ConvexType::Int64 => Some(any(quote! { i64 })),
+      // Special floats round-trip but never compare equal.
+      | ConvexType::Number => Some(quote! {
+        ::proptest::num::f64::NORMAL
+          | ::proptest::num::f64::SUBNORMAL
+          | ::proptest::num::f64::ZERO
+      }),
+      | ConvexType::Bool => Some(any(quote! { bool })),
+      | ConvexType::String => Some(any(quote! { String })),
+      | ConvexType::StringLiteral(_)
+      | ConvexType::BoolLiteral(_)
+      | ConvexType::IntLiteral(_)
+      | ConvexType::FloatLiteral(_) => {
+        let literal = self.print_literal();
+        Some(quote! { ::proptest::strategy::LazyJust::new(|| #literal) })
+      },
+      | ConvexType::Optional(child) | ConvexType::Nullable(child) => child
+        .t
+        .print_strategy()
+        .map(|strategy| quote! { ::proptest::option::of(#strategy) }),
+      | ConvexType::Array(child) => child.t.print_strategy().map(|strategy| {
+        quote! { ::proptest::collection::vec(#strategy, 0..4) }
+      }),
+      | ConvexType::Record(keys, child) => {
+        let keys = keys.print_strategy()?;
+        child.t.print_strategy().map(|strategy| {
+          quote! { ::proptest::collection::btree_map(#keys, #strategy, 0..4) }
+        })
+      },
+      | ConvexType::Object(_) | ConvexType::Union(_) => None,
+    }
+  }
+
+  /// Prints a fixture expression for simple types using `fixtures`, objects
+  /// and unions depend on field.name like `print`.
+  fn print_fixture(&self, field_name: &str) -> Option<TokenStream> {
+    match &self {
+      | ConvexType::Id(_) => Some(quote! { fixtures.id() }),
+      | ConvexType::Null => Some(quote! { () }),
+      | ConvexType::Int64 => Some(quote! { fixtures.int64() }),
+      | ConvexType::Number => Some(quote! { fixtures.number() }),
+      | ConvexType::Bool => Some(quote! { fixtures.boolean() }),
+      | ConvexType::String => Some(quote! { fixtures.string(#field_name) }),
+      | ConvexType::StringLiteral(_)
+      | ConvexType::BoolLiteral(_)
+      | ConvexType::IntLiteral(_)
+      | ConvexType::FloatLiteral(_) => self.print_literal(),
+      | ConvexType::Optional(_)
+      | ConvexType::Nullable(_)
+      | ConvexType::Array(_)
+      | ConvexType::Record(..)
+      | ConvexType::Object(_)
+      | ConvexType::Union(_) => None,
+    }
+  }
+
+  /// Prints the only valid value of a literal type.
+  fn print_literal(&self) -> Option<TokenStream> {
+    match &self {
+      | ConvexType::StringLiteral(s) => {
+        Some(quote! { ::std::string::String::from(#s) })
+      },
+      | ConvexType::BoolLiteral(b) => Some(quote! { #b }),
+      | ConvexType::IntLiteral(i) => Some(quote! { #i }),
+      | ConvexType::FloatLiteral(n) => Some(quote! { #n }),
+      | _ => None,
+    }
+  }
+
+  /// Marks this and every nested object as strict.
+  fn set_strict(&mut self) {
+    match self {
+      | ConvexType::Object(object) => {
+        // Nested objects with a rest field accept unknown keys by
+        // definition, the model itself is checked while parsing.
+        object.strict = object.rest.is_none();
+        for field in object.fields.iter_mut() {
+          field.t.set_strict();
+        }
+      },
+      | ConvexType::Union(types) => {
+        for t in types.iter_mut() {
+          t.set_strict();
+        }
+      },
+      | ConvexType::Optional(child)
+      | ConvexType::Nullable(child)
+      | ConvexType::Array(child)
+      | ConvexType::Record(_, child) => child.t.set_strict(),
+      | _ => {},
+    }
+  }
+
+  /// Prints an expression converting `value`, a reference to the rust
+  /// representation of this type, into a `convex::Value`.
+  pub fn print_to_convex_value(&self, value: TokenStream) -> TokenStream {
+    match &self {
+      | ConvexType::Id(_)
+      | ConvexType::String
+      | ConvexType::StringLiteral(_) => {
+        quote! { ::convex::Value::String((#value).clone()) }
+      },
+      | ConvexType::Null => quote! { ::convex::Value::Null },
+      | ConvexType::Int64 | ConvexType::IntLiteral(_) => {
+        quote! { ::convex::Value::Int64(*#value) }
+      },
+      | ConvexType::Number | ConvexType::FloatLiteral(_) => {
+        quote! { ::convex::Value::Float64(*#value) }
+      },
+      | ConvexType::Bool | ConvexType::BoolLiteral(_) => {
+        quote! { ::convex::Value::Boolean(*#value) }
+      },
+      | ConvexType::Object(_) | ConvexType::Union(_) => {
+        quote! { (#value).to_convex_value() }
+      },
+      | ConvexType::Array(child) => {
+        let child_value = child.t.print_to_convex_value(quote! { value });
+        quote! {
+          ::convex::Value::Array((#value).iter().map(|value| #child_value).collect())
+        }
+      },
+      | ConvexType::Record(_, child) => {
+        let child_value = child.t.print_to_convex_value(quote! { value });
+        quote! {
+          ::convex::Value::Object(
+            (#value).iter().map(|(key, value)| (key.clone(), #child_value)).collect()
+          )
+        }
+      },
+      // Unlike optional fields, `None` is sent as an explicit null.
+      | ConvexType::Nullable(child) => {
+        let child_value = child.t.print_to_convex_value(quote! { value });
+        quote! {
+          match #value {
+            | ::core::option::Option::Some(value) => #child_value,
+            | ::core::option::Option::None => ::convex::Value::Null,
+          }
+        }
+      },
+      | ConvexType::Optional(_) => {
+        panic!("Internal Error: Optional fields are printed by their object")
+      },
+    }
+  }
+
+  /// Prints a serializable expression for `value`, a reference to the rust
+  /// representation of this type.
+  ///
+  /// Numbers go through `convex::Value` so that `NaN`, `Infinity` and `-0.0`
+  /// use Convex's `{"$float": ...}` encoding instead of becoming `null`.
+  fn print_to_json_value(&self, value: TokenStream) -> TokenStream {
+    match &self {
+      | ConvexType::Number | ConvexType::FloatLiteral(_) => {
+        quote! { ::serde_json::Value::from(::convex::Value::Float64(*#value)) }
+      },
+      | ConvexType::Array(child) => {
+        let child_value = child.t.print_to_json_value(quote! { value });
+        quote! {
+          (#value).iter().map(|value| #child_value).collect::<::std::vec::Vec<_>>()
+        }
+      },
+      | ConvexType::Record(_, child) => {
+        let child_value = child.t.print_to_json_value(quote! { value });
+        quote! {
+          (#value)
+            .iter()
+            .map(|(key, value)| (key, #child_value))
+            .collect::<::std::collections::BTreeMap<_, _>>()
+        }
+      },
+      | ConvexType::Nullable(child) => {
+        let child_value = child.t.print_to_json_value(quote! { value });
+        quote! { (#value).as_ref().map(|value| #child_value) }
+      },
+      | _ => value,
+    }
+  }
+
+  /// Whether the rust representation holds numbers outside of a nested struct,
+  /// which `print_to_json_value` may encode as `{"$float": ...}`.
+  fn contains_float(&self) -> bool {
+    match &self {
+      | ConvexType::Number | ConvexType::FloatLiteral(_) => true,
+      | ConvexType::Optional(child)
+      | ConvexType::Nullable(child)
+      | ConvexType::Array(child)
+      | ConvexType::Record(_, child) => child.t.contains_float(),
+      | _ => false,
+    }
+  }
+
+  /// Checks the field path constants of every object within this type, see
+  /// `ConvexObject::check_field_paths`.
+  fn check_field_paths(&self, span: Span) -> Result<()> {
+    match &self {
+      | ConvexType::Object(object) => object.check_field_paths(span),
+      | ConvexType::Union(types) => {
+        types.iter().try_for_each(|t| t.check_field_paths(span))
+      },
+      | ConvexType::Optional(child)
+      | ConvexType::Nullable(child)
+      | ConvexType::Array(child)
+      | ConvexType::Record(_, child) => child.t.check_field_paths(span),
+      | _ => Ok(()),
+    }
+  }
+
+  /// Prints the TypeScript validator source for this type, e.g.
+  /// `v.object({ name: v.string() })`.
+  fn print_ts(&self) -> String {
+    match &self {
+      | ConvexType::Id(table) => format!("v.id({})", print_ts_string(table)),
+      | ConvexType::Null => "v.null()".into(),
+      | ConvexType::Int64 => "v.int64()".into(),
+      | ConvexType::Number => "v.number()".into(),
+      | ConvexType::Bool => "v.boolean()".into(),
+      | ConvexType::String => "v.string()".into(),
+      | ConvexType::StringLiteral(s) => {
+        format!("v.literal({})", print_ts_string(s))
+      },
+      | ConvexType::BoolLiteral(b) => format!("v.literal({})", b),
+      | ConvexType::IntLiteral(i) => format!("v.literal({}n)", i),
+      // Display never adds `.0`, like TypeScript prints numbers.
+      | ConvexType::FloatLiteral(n) => format!("v.literal({})", n),
+      | ConvexType::Optional(child) => {
+        format!("v.optional({})", child.t.print_ts())
+      },
+      | ConvexType::Array(child) => format!("v.array({})", child.t.print_ts()),
+      | ConvexType::Record(keys, child) => {
+        format!("v.record({}, {})", keys.print_ts(), child.t.print_ts())
+      },
+      | ConvexType::Nullable(child) => {
+        format!("v.nullable({})", child.t.print_ts())
+      },
+      | ConvexType::Union(types) => {
+        let types: Vec<String> = types.iter().map(|t| t.print_ts()).collect();
+        format!("v.union({})", types.join(", "))
+      },
+      // Rest fields have no TypeScript equivalent and are left out.
+      | ConvexType::Object(object) if object.fields.is_empty() => {
+        "v.object({})".into()
+      },
+      | ConvexType::Object(object) => {
+        let fields: Vec<String> = object
+          .fields
+          .iter()
+          .map(|field| format!("{}: {}", field.name.id, field.t.print_ts()))
+          .collect();
+        format!("v.object({{ {} }})", fields.join(", "))
+      },
+    }
+  }
+}
+
+/// Prints a TypeScript string literal.
+fn print_ts_string(s: &str) -> String {
+  let mut out = String::from("\"");
+  for c in s.chars() {
+    match c {
+      | '"' => out.push_str("\\\""),
+      | '\\' => out.push_str("\\\\"),
+      | '\n' => out.push_str("\\n"),
+      | '\r' => out.push_str("\\r"),
+      | '\t' => out.push_str("\\t"),
+      | c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+      | c => out.push(c),
+    }
+  }
+  out.push('"');
+  out
+}
+
+impl ConvexObject {
+  fn from_validator(name: &ConvexName, object: ObjectValidator) -> Self {
+    let fields = object
+      .fields
+      .into_iter()
+      .map(|field| {
+        let name = ConvexName { path: name.full_path(), id: field.name };
+        let t = ConvexType::from_validator(&name, field.validator);
+        ConvexField { name, t }
+      })
+      .collect();
+    ConvexObject { fields, strict: object.strict, rest: object.rest }
+  }
+
+  /// Fields that must be passed to constructors, literals and optional fields
+  /// are filled automatically.
+  fn constructor_fields(&self) -> Vec<&ConvexField> {
+    self
+      .fields
+      .iter()
+      .filter(|field| {
+        field.t.print_literal().is_none()
+          && !matches!(field.t, ConvexType::Optional(_))
+      })
+      .collect()
+  }
+
+  /// The value of the first string literal field, used to name union branches.
+  fn discriminant(&self) -> Option<&String> {
+    self.fields.iter().find_map(|field| match &field.t {
+      | ConvexType::StringLiteral(s) => Some(s),
+      | _ => None,
+    })
+  }
+
+  /// Prints a `declared_fields` slice holding the declared field names.
+  fn print_declared_fields(&self) -> TokenStream {
+    let field_names = self.fields.iter().map(|field| &field.name.id);
+    quote! {
+      let declared_fields: &[&str] = &[#( #field_names ),*];
+    }
+  }
+
+  /// Every (nested) field path with the field holding its value. Optional
+  /// fields use their inner field and nullable objects are recursed into,
+  /// array items, record values and duplicate paths from union branches with
+  /// a different type are left out.
+  pub fn field_paths(&self) -> Vec<(Vec<String>, &ConvexField)> {
+    let mut paths: Vec<(Vec<String>, &ConvexField)> = Vec::new();
+    let mut conflicts: Vec<Vec<String>> = Vec::new();
+    for field in &self.fields {
+      let field = match &field.t {
+        | ConvexType::Optional(child) => child.as_ref(),
+        | _ => field,
+      };
+      let path = vec![field.name.id.clone()];
+      paths.push((path.clone(), field));
+
+      let inner = match &field.t {
+        | ConvexType::Nullable(child) => &child.t,
+        | t => t,
+      };
+      let objects: Vec<&ConvexObject> = match inner {
+        | ConvexType::Object(object) => vec![object],
+        | ConvexType::Union(types) => types
+          .iter()
+          .filter_map(|t| match t {
+            | ConvexType::Object(object) => Some(object),
+            | _ => None,
+          })
+          .collect(),
+        | _ => Vec::new(),
+      };
+      for object in objects {
+        for (nested_path, nested_field) in object.field_paths() {
+          let nested_path = [path.clone(), nested_path].concat();
+          if conflicts.contains(&nested_path) {
+            continue;
+          }
+          let existing = paths.iter().find(|(path, _)| *path == nested_path);
+          match existing {
+            | None => paths.push((nested_path, nested_field)),
+            | Some((_, existing)) => {
+              let existing_type = existing.print_type().to_string();
+              if existing_type != nested_field.print_type().to_string() {
+                paths.retain(|(path, _)| *path != nested_path);
+                conflicts.push(nested_path);
+              }
+            },
+          }
+        }
+      }
+    }
+    paths
+  }
+
+  /// Checks the `FIELD_<PATH>` constants of this and every nested object
+  /// have distinct names, like `firstName` and `first_name` would not.
+  fn check_field_paths(&self, span: Span) -> Result<()> {
+    let mut names: Vec<(Ident, String)> = Vec::new();
+    for (path, _) in self.field_paths() {
+      let name = to_field_path_const(&path);
+      let path = path.join(".");
+      if let Some((_, other)) = names.iter().find(|(other, _)| *other == name) {
+        return Err(Error::new(
+          span,
+          format!(
+            "Field paths '{}' and '{}' both become the constant `{}`",
+            other, path, name
+          ),
+        ));
+      }
+      names.push((name, path));
+    }
+    for field in &self.fields {
+      field.t.check_field_paths(span)?;
+    }
+    Ok(())
+  }
 }
 
 impl ConvexName {
-  fn to_struct_name(&self) -> Ident {
+  pub fn to_struct_name(&self) -> Ident {
     let path_parts: Vec<String> =
       self.path.iter().map(|p| capitalize_first_char(p)).collect();
     let id_part = capitalize_first_char(&self.id);
@@ -75,29 +598,191 @@ impl ConvexName {
     Ident::new(s.as_str(), Span::call_site())
   }
 
-  fn to_field_name(&self) -> Ident {
+  pub fn to_field_name(&self) -> Ident {
     Ident::new(self.id.as_str(), Span::call_site())
   }
 
-  fn full_path(&self) -> Vec<String> {
+  pub fn full_path(&self) -> Vec<String> {
     let mut v = self.path.clone();
     v.push(self.id.clone());
     v
   }
 }
 
-impl Parse for ConvexField {
+/// A top level model, optionally versioned like `User v2 { ... }`.
+pub struct ConvexModel {
+  pub model: ConvexField,
+  pub indexes: Vec<ConvexIndex>,
+  // The name without its version, `User` for `UserV2`.
+  pub base: Ident,
+  pub version: Option<u32>,
+}
+
+impl Parse for ConvexModel {
   fn parse(input: ParseStream) -> Result<Self> {
+    // #[strict] Name v2 { ... }
+    // ^^^^^^^^^
+    let strict_span = input.span();
+    let strict = parse::parse_strict_attribute(input)?;
+
+    // #[strict] Name v2 { ... }
+    //           ^^^^
     let ident = Ident::parse(input)?;
-    let name = ConvexName { path: Vec::new(), id: ident.to_string() };
+
+    // #[strict] Name v2 { ... }
+    //                ^^
+    let version = if input.peek(Ident) {
+      let version_ident = Ident::parse(input)?;
+      let version = version_ident
+        .to_string()
+        .strip_prefix('v')
+        .and_then(|version| version.parse::<u32>().ok())
+        .filter(|version| *version > 0);
+      match version {
+        | Some(version) => Some(version),
+        | None => {
+          return Err(Error::new_spanned(
+            &version_ident,
+            "Expected a version like v1",
+          ));
+        },
+      }
+    } else {
+      None
+    };
+
+    // Versions are separate structs, like `UserV2`.
+    let id = match version {
+      | Some(version) => format!("{}V{}", ident, version),
+      | None => ident.to_string(),
+    };
+    let name = ConvexName { path: Vec::new(), id };
 
     let content;
     let _ = syn::braced!(content in input);
-    let ts = Self::parse_comma_separated(&name, &content, |name, b| {
-      Self::parse_child(name, b)
-    })?;
+    let object = ConvexField::parse_object(&name, &content, false)?;
+    if strict && object.rest.is_some() {
+      return Err(Error::new(
+        strict_span,
+        "#[strict] objects may not have a rest field",
+      ));
+    }
+
+    // User { ... }.index("by_name", ["name"])
+    //            ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    let mut indexes = Vec::new();
+    while input.peek(Token![.]) {
+      let index = ConvexIndex::parse(input)?;
+      index.resolve_fields(&object)?;
+      indexes.push(index);
+    }
+
+    let mut t = ConvexType::Object(object);
+    if strict {
+      t.set_strict();
+    }
+    let model = ConvexField { name, t };
+
+    // Index structs share the namespace of the model's other types.
+    let struct_name = model.name.to_struct_name();
+    let mut struct_names = model.struct_names();
+    struct_names.push(model.patch_name().to_string());
+    for (i, index) in indexes.iter().enumerate() {
+      let index_name = index.name.value();
+      if indexes[..i].iter().any(|other| other.name.value() == index_name) {
+        return Err(Error::new_spanned(
+          &index.name,
+          format!("Duplicate index name '{}'", index_name),
+        ));
+      }
+      let index_struct = index.struct_name(&struct_name).to_string();
+      if struct_names.contains(&index_struct) {
+        return Err(Error::new_spanned(
+          &index.name,
+          format!(
+            "Index '{}' generates `{}`, which is already generated for this \
+             model",
+            index_name, index_struct
+          ),
+        ));
+      }
+      struct_names.push(index_struct);
+    }
+    Ok(Self { model, indexes, base: ident, version })
+  }
+}
+
+impl ConvexModel {
+  pub fn print(&self) -> Vec<TokenStream> {
+    let mut items = self.model.print();
+    if let ConvexType::Object(object) = &self.model.t {
+      let struct_name = self.model.name.to_struct_name();
+      for index in &self.indexes {
+        let fields = index
+          .resolve_fields(object)
+          .expect("Internal Error: Index fields are checked while parsing");
+        items.push(index.print(&struct_name, &fields));
+      }
+    }
+    if let Some(version) = self.version {
+      items.push(self.print_version(version));
+    }
+    items
+  }
+
+  fn print_version(&self, version: u32) -> TokenStream {
+    let struct_name = self.model.name.to_struct_name();
+    // Older versions are upgraded with the user's `From` impl.
+    let fallback = match version {
+      | 1 => quote! { ::core::result::Result::Err(error) },
+      | _ => {
+        let previous = Ident::new(
+          format!("{}V{}", self.base, version - 1).as_str(),
+          Span::call_site(),
+        );
+        quote! {
+          match #previous::from_any_version(value) {
+            | ::core::result::Result::Ok((model, version)) => {
+              ::core::result::Result::Ok((::core::convert::From::from(model), version))
+            },
+            | ::core::result::Result::Err(_) => ::core::result::Result::Err(error),
+          }
+        }
+      },
+    };
+    quote! {
+      #[allow(non_snake_case)]
+      impl #struct_name {
+        pub const VERSION: u32 = #version;
+
+        /// Decodes this version or an older one, upgrading older versions
+        /// through their `From` impls. Returns the version that was read.
+        pub fn from_any_version(
+          value: &::convex::Value
+        ) -> ::core::result::Result<(Self, u32), ::anyhow::Error> {
+          match Self::from_convex_value(value) {
+            | ::core::result::Result::Ok(model) => {
+              ::core::result::Result::Ok((model, #version))
+            },
+            | ::core::result::Result::Err(error) => #fallback,
+          }
+        }
+      }
+    }
+  }
+}
 
-    Ok(Self { name, t: ConvexType::Object(ts) })
+impl Parse for ConvexField {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.span();
+    let model = ConvexModel::parse(input)?;
+    if model.version.is_some() || !model.indexes.is_empty() {
+      return Err(Error::new(
+        span,
+        "Versions and indexes are only supported by models",
+      ));
+    }
+    Ok(model.model)
   }
 }
 
@@ -107,6 +792,7 @@ impl ConvexField {
     let struct_name_str = struct_name.to_string();
     let mut structs = Vec::new();
     let mut impls = Vec::new();
+    let mut union_constructors = Vec::new();
     let ignore_attributes = quote! {
       #[allow(non_snake_case)]
     };
@@ -116,10 +802,21 @@ impl ConvexField {
     };
 
     match &self.t {
-      | ConvexType::Object(fields) => {
-        structs.append(&mut Self::print_structs(fields, &struct_name));
-        impls.push(Self::print_to_json_impl(fields, &struct_name));
-        impls.push(Self::print_from_convex_value(fields, &struct_name));
+      | ConvexType::Object(object) => {
+        structs.append(&mut Self::print_structs(object, &struct_name));
+        impls.push(Self::print_to_json_impl(&struct_name));
+        impls.push(Self::print_from_convex_value(object, &struct_name));
+        impls.push(Self::print_to_convex_value(object, &struct_name));
+        impls.push(Self::print_field_paths(object, &struct_name));
+        // Only top level objects are documents that can be patched.
+        if self.name.path.is_empty() {
+          let patch_name = self.patch_name();
+          structs.append(&mut Self::print_patch(
+            object,
+            &struct_name,
+            &patch_name,
+          ));
+        }
       },
       | ConvexType::Union(types) => {
         let field_name = self.name.to_field_name();
@@ -128,7 +825,10 @@ impl ConvexField {
         let mut extract_arms = Vec::new();
         let mut json_arms: Vec<TokenStream> = Vec::new();
         let mut serialize_arms: Vec<TokenStream> = Vec::new();
+        let mut convex_arms: Vec<TokenStream> = Vec::new();
         let mut as_fns: Vec<TokenStream> = Vec::new();
+        let mut constructors: Vec<(usize, Option<&String>, TokenStream)> =
+          Vec::new();
         let mut i = 0;
         for t in types {
           i += 1;
@@ -141,6 +841,54 @@ impl ConvexField {
             format!("{}Variant{}", struct_name, i).as_str(),
             Span::call_site(),
           );
+          match t {
+            | ConvexType::Object(object) => {
+              let params =
+                object.constructor_fields().into_iter().map(|field| {
+                  let field_name = field.name.to_field_name();
+                  let field_type = field.print_type();
+                  quote! { #field_name: #field_type }
+                });
+              let args = object
+                .constructor_fields()
+                .into_iter()
+                .map(|field| field.name.to_field_name());
+              constructors.push((
+                i,
+                object.discriminant(),
+                quote! {
+                  (#( #params ),*) -> Self {
+                    #struct_name::#branch_name(#full_branch_name::new(#( #args ),*))
+                  }
+                },
+              ));
+            },
+            | t => {
+              if let Some(literal) = t.print_literal() {
+                let discriminant = match t {
+                  | ConvexType::StringLiteral(s) => Some(s),
+                  | _ => None,
+                };
+                constructors.push((
+                  i,
+                  discriminant,
+                  quote! {
+                    () -> Self {
+                      #struct_name::#branch_name(#literal)
+                    }
+                  },
+                ));
+              }
+            },
+          };
+          if let ConvexType::Array(_) | ConvexType::Record(..) = t {
+            if t.print().is_none() {
+              panic!(
+                "Internal Error: Arrays and records of objects or unions are \
+                 rejected while parsing"
+              )
+            }
+          }
           let branch_type = t.print();
           match branch_type {
             | Some(branch_type) => {
@@ -155,6 +903,9 @@ impl ConvexField {
                 serialize_arms.push(quote! {
                   | #struct_name::#branch_name => ().serialize(serializer),
                 });
+                convex_arms.push(quote! {
+                  | #struct_name::#branch_name => ::convex::Value::Null,
+                });
                 as_fns.push(quote! {
                   pub fn #as_name(&self) -> ::core::result::Result<(), ::anyhow::Error> {
                     if let #struct_name::#branch_name = self {
@@ -169,14 +920,19 @@ impl ConvexField {
                   }
                 });
               } else {
+                let json_value = t.print_to_json_value(quote! { value });
+                let convex_value = t.print_to_convex_value(quote! { value });
                 enum_kinds.push(quote! {
                   #branch_name(#branch_type),
                 });
                 json_arms.push(quote! {
-                  | #struct_name::#branch_name(value) => ::serde_json::json!(value),
+                  | #struct_name::#branch_name(value) => ::serde_json::json!(#json_value),
                 });
                 serialize_arms.push(quote! {
-                  | #struct_name::#branch_name(ref value) => value.serialize(serializer),
+                  | #struct_name::#branch_name(ref value) => #json_value.serialize(serializer),
+                });
+                convex_arms.push(quote! {
+                  | #struct_name::#branch_name(value) => #convex_value,
                 });
                 as_fns.push(quote! {
                   pub fn #as_name(&self) -> ::core::result::Result<#branch_type, ::anyhow::Error> {
@@ -203,6 +959,9 @@ impl ConvexField {
               serialize_arms.push(quote! {
                 | #struct_name::#branch_name(ref value) => value.serialize(serializer),
               });
+              convex_arms.push(quote! {
+                | #struct_name::#branch_name(value) => value.to_convex_value(),
+              });
               // TODO: Probably doing too much cloning.
               as_fns.push(quote! {
                 pub fn #as_name(&self) -> ::core::result::Result<#full_branch_name, ::anyhow::Error> {
@@ -245,10 +1004,19 @@ impl ConvexField {
               | ::convex::Value::Int64(value) if value.clone() == #i => {
                 ::core::result::Result::Ok(#struct_name::#branch_name(value.clone()))
               },
-              | ::convex::Value::Float64(value) if value.clone() as i64 == #i => {
+              | ::convex::Value::Float64(value)
+                if value.fract() == 0.0 && value.clone() as i64 == #i => {
                 ::core::result::Result::Ok(#struct_name::#branch_name(value.clone() as i64))
               },
             }),
+            | ConvexType::FloatLiteral(n) => extract_arms.push(quote! {
+              | ::convex::Value::Float64(value) if value.clone() == #n => {
+                ::core::result::Result::Ok(#struct_name::#branch_name(value.clone()))
+              },
+              | ::convex::Value::Int64(value) if value.clone() as f64 == #n => {
+                ::core::result::Result::Ok(#struct_name::#branch_name(value.clone() as f64))
+              },
+            }),
             // TODO: Should this accept Int64 or just Float64?
             | ConvexType::Number => extract_arms.push(quote! {
               | ::convex::Value::Int64(value) => {
@@ -279,9 +1047,15 @@ impl ConvexField {
               },
             }),
 
-            | ConvexType::Object(fields) => {
-              structs.append(&mut Self::print_structs(fields, &full_branch_name));
-              impls.push(Self::print_from_convex_value(fields, &full_branch_name));
+            | ConvexType::Object(object) => {
+              structs.append(&mut Self::print_structs(object, &full_branch_name));
+              impls.push(Self::print_from_convex_value(object, &full_branch_name));
+              impls.push(Self::print_to_convex_value(object, &full_branch_name));
+              impls.push(Self::print_field_paths(object, &full_branch_name));
+              impls.push(Self::print_fixture_fns(t, &full_branch_name));
+              if cfg!(feature = "proptest") {
+                impls.push(Self::print_arbitrary(t, &full_branch_name));
+              }
               extract_arms.push(quote! {
                 | value if #full_branch_name::from_convex_value(value).is_ok() => {
                   Ok(#struct_name::#branch_name(#full_branch_name::from_convex_value(value)?))
@@ -289,84 +1063,748 @@ impl ConvexField {
               });
             },
 
-            | ConvexType::Optional(_) => panic!("Unions may not contain optional branches"),
-            | ConvexType::Union(_) => panic!("Unions may not directly contain other unions, put other types between them"),
-          };
-        }
+            | ConvexType::Array(_) => {
+              let branch_type = t.print();
+              let branch_value = Ident::new("branch_value", Span::call_site());
+              let extract = Self::print_extract_type(
+                t,
+                branch_value.clone(),
+                quote! { value },
+                field_name_str.clone(),
+              );
+              let try_extract = quote! {
+                |value: &::convex::Value| -> ::core::result::Result<#branch_type, ::anyhow::Error> {
+                  let value = ::core::option::Option::Some(value);
+                  #extract
+                  ::core::result::Result::Ok(#branch_value)
+                }
+              };
+              extract_arms.push(quote! {
+                | value @ ::convex::Value::Array(_) if (#try_extract)(value).is_ok() => {
+                  ::core::result::Result::Ok(#struct_name::#branch_name((#try_extract)(value)?))
+                },
+              });
+            },
+
+            | ConvexType::Record(..) => {
+              let branch_type = t.print();
+              let branch_value = Ident::new("branch_value", Span::call_site());
+              let extract = Self::print_extract_type(
+                t,
+                branch_value.clone(),
+                quote! { value },
+                field_name_str.clone(),
+              );
+              let try_extract = quote! {
+                |value: &::convex::Value| -> ::core::result::Result<#branch_type, ::anyhow::Error> {
+                  let value = ::core::option::Option::Some(value);
+                  #extract
+                  ::core::result::Result::Ok(#branch_value)
+                }
+              };
+              extract_arms.push(quote! {
+                | value @ ::convex::Value::Object(_) if (#try_extract)(value).is_ok() => {
+                  ::core::result::Result::Ok(#struct_name::#branch_name((#try_extract)(value)?))
+                },
+              });
+            },
+
+            | ConvexType::Optional(_) => panic!("Internal Error: Optional union branches are rejected while parsing"),
+            | ConvexType::Nullable(_) => panic!("Internal Error: Nullable union branches are rejected while parsing"),
+            | ConvexType::Union(_) => panic!("Internal Error: Nested unions are rejected while parsing"),
+          };
+        }
+
+        // Constructors are named once every other method is known.
+        union_constructors = constructors;
+
+        structs.push(quote! {
+          #ignore_attributes
+          #enum_struct_attributes
+          pub enum #struct_name {
+            #( #enum_kinds )*
+          }
+        });
+
+        impls.push(quote! {
+          #ignore_attributes
+          impl ::serde::Serialize for #struct_name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where S: ::serde::Serializer {
+              match *self {
+                #( #serialize_arms )*
+              }
+            }
+          }
+        });
+
+        impls.push(quote! {
+          #ignore_attributes
+          impl #struct_name {
+            pub fn from_convex_value(
+              value: &::convex::Value
+            ) -> ::core::result::Result<Self, ::anyhow::Error> {
+              match value {
+                #( #extract_arms )*
+                | _ => {
+                  Err(::anyhow::anyhow!("Invalid union type for '{}'", #field_name_str))
+                },
+              }
+            }
+
+            pub fn to_convex_value(&self) -> ::convex::Value {
+              match self {
+                #( #convex_arms )*
+              }
+            }
+
+            #(
+              #as_fns
+            )*
+          }
+        });
+
+        impls.push(quote! {
+          #ignore_attributes
+          impl ::core::convert::From<#struct_name> for ::serde_json::Value {
+            fn from(value: #struct_name) -> Self {
+              match &value {
+                #( #json_arms )*
+              }
+            }
+          }
+        })
+      },
+      | _ => {
+        panic!(
+          "Internal Error: Should have a complex field like object or union"
+        )
+      },
+    }
+
+    let validator_ts = self.t.print_ts();
+    let validator = self.t.to_validator();
+    let schema_hash = validator.schema_hash();
+    impls.push(quote! {
+      #ignore_attributes
+      impl #struct_name {
+        /// The Convex validator source this type was generated from.
+        pub const CONVEX_VALIDATOR_TS: &'static str = #validator_ts;
+
+        /// A stable fingerprint of this type's validator, see
+        /// `ragkit_convex_runtime::Validator::schema_hash`.
+        pub const SCHEMA_HASH: u64 = #schema_hash;
+
+        /// Describes this type for generic tooling.
+        pub fn convex_schema() -> &'static ::ragkit_convex_runtime::Validator {
+          static SCHEMA: ::std::sync::OnceLock<
+            ::ragkit_convex_runtime::Validator,
+          > = ::std::sync::OnceLock::new();
+          SCHEMA.get_or_init(|| #validator)
+        }
+
+        /// A JSON Schema (draft 2020-12) for the JSON this type serializes to.
+        pub fn json_schema() -> ::serde_json::Value {
+          let mut schema = Self::convex_schema().to_json_schema();
+          schema["$schema"] = ::serde_json::Value::from(
+            "https://json-schema.org/draft/2020-12/schema",
+          );
+          schema["title"] = ::serde_json::Value::from(#struct_name_str);
+          schema
+        }
+      }
+    });
+
+    impls.push(Self::print_fixture_fns(&self.t, &struct_name));
+
+    if cfg!(feature = "proptest") {
+      impls.push(Self::print_arbitrary(&self.t, &struct_name));
+    }
+
+    if cfg!(feature = "schemars") {
+      impls.push(quote! {
+        #ignore_attributes
+        impl ::schemars::JsonSchema for #struct_name {
+          fn schema_name() -> ::std::borrow::Cow<'static, str> {
+            ::std::borrow::Cow::Borrowed(#struct_name_str)
+          }
+
+          fn json_schema(
+            _generator: &mut ::schemars::SchemaGenerator,
+          ) -> ::schemars::Schema {
+            let mut schema = Self::convex_schema().to_json_schema();
+            schema["title"] = ::serde_json::Value::from(#struct_name_str);
+            ::schemars::Schema::try_from(schema)
+              .expect("JSON Schemas are always objects")
+          }
+        }
+      });
+    }
+
+    if !union_constructors.is_empty() {
+      impls.push(Self::print_union_constructors(
+        &struct_name,
+        union_constructors,
+        &impls,
+      ));
+    }
+
+    [structs, impls].concat()
+  }
+
+  /// Prints union constructors, named after their literal when it is a
+  /// usable, unique function name that no generated method of `struct_name`
+  /// in `impls` already uses, and `variant_N` otherwise.
+  fn print_union_constructors(
+    struct_name: &Ident,
+    constructors: Vec<(usize, Option<&String>, TokenStream)>,
+    impls: &[TokenStream],
+  ) -> TokenStream {
+    let mut reserved: Vec<String> = impls
+      .iter()
+      .filter_map(|tokens| syn::parse2::<syn::File>(tokens.clone()).ok())
+      .flat_map(|file| file.items)
+      .filter_map(|item| match item {
+        | syn::Item::Impl(item) => Some(item),
+        | _ => None,
+      })
+      .filter(|item| match item.self_ty.as_ref() {
+        | syn::Type::Path(path) => path.path.is_ident(struct_name),
+        | _ => false,
+      })
+      .flat_map(|item| item.items)
+      .filter_map(|item| match item {
+        | syn::ImplItem::Fn(method) => Some(method.sig.ident.to_string()),
+        | _ => None,
+      })
+      .collect();
+    reserved
+      .extend(constructors.iter().map(|(i, _, _)| format!("variant_{}", i)));
+    let names: Vec<Option<Ident>> = constructors
+      .iter()
+      .map(|(_, discriminant, _)| {
+        discriminant
+          .and_then(|d| to_fn_name(d))
+          .filter(|name| !reserved.contains(&name.to_string()))
+      })
+      .collect();
+    let constructor_names: Vec<Option<Ident>> = names
+      .iter()
+      .map(|name| {
+        name.clone().filter(|name| {
+          names.iter().filter(|other| other.as_ref() == Some(name)).count() == 1
+        })
+      })
+      .collect();
+    let constructor_fns = constructors.iter().zip(constructor_names).map(
+      |((i, _, signature), name)| {
+        let name = name.unwrap_or_else(|| {
+          Ident::new(format!("variant_{}", i).as_str(), Span::call_site())
+        });
+        quote! {
+          #[allow(clippy::too_many_arguments)]
+          pub fn #name #signature
+        }
+      },
+    );
+    quote! {
+      #[allow(non_snake_case)]
+      impl #struct_name {
+        #( #constructor_fns )*
+      }
+    }
+  }
+
+  /// Prints `new` taking every required field and filling literals, optional
+  /// fields start as `None` and can be set with `with_<field>`.
+  fn print_constructors(
+    object: &ConvexObject,
+    struct_name: &Ident,
+  ) -> TokenStream {
+    let ignore_attributes = quote! {
+      #[allow(non_snake_case)]
+    };
+    let mut params = Vec::new();
+    let mut field_values = Vec::new();
+    let mut with_fns = Vec::new();
+    for field in &object.fields {
+      let field_name = field.name.to_field_name();
+      let field_type = field.print_type();
+      if let Some(literal) = field.t.print_literal() {
+        field_values.push(quote! { #field_name: #literal, });
+      } else if let ConvexType::Optional(_) = &field.t {
+        let with_name = Ident::new(
+          format!("with_{}", field_name).as_str(),
+          Span::call_site(),
+        );
+        field_values.push(quote! {
+          #field_name: ::core::option::Option::None,
+        });
+        with_fns.push(quote! {
+          pub fn #with_name(mut self, #field_name: #field_type) -> Self {
+            self.#field_name = #field_name;
+            self
+          }
+        });
+      } else {
+        params.push(quote! { #field_name: #field_type });
+        field_values.push(quote! { #field_name, });
+      }
+    }
+    if let Some(rest) = &object.rest {
+      let rest = Ident::new(rest, Span::call_site());
+      field_values.push(quote! {
+        #rest: ::std::collections::BTreeMap::new(),
+      });
+    }
+    quote! {
+      #ignore_attributes
+      impl #struct_name {
+        #[allow(clippy::too_many_arguments)]
+        pub fn new(#( #params ),*) -> Self {
+          #struct_name {
+            #( #field_values )*
+          }
+        }
+
+        #( #with_fns )*
+      }
+    }
+  }
+
+  /// Prints a proptest strategy for the rust type of this field.
+  fn print_strategy(&self) -> TokenStream {
+    match &self.t {
+      | ConvexType::Optional(child) | ConvexType::Nullable(child) => {
+        let strategy = child.print_strategy();
+        quote! { ::proptest::option::of(#strategy) }
+      },
+      | ConvexType::Array(child) => {
+        let strategy = child.print_strategy();
+        quote! { ::proptest::collection::vec(#strategy, 0..4) }
+      },
+      | ConvexType::Record(keys, child) => {
+        let keys = keys
+          .print_strategy()
+          .expect("Internal Error: Expected a simple type");
+        let strategy = child.print_strategy();
+        quote! { ::proptest::collection::btree_map(#keys, #strategy, 0..4) }
+      },
+      | ConvexType::Object(_) | ConvexType::Union(_) => {
+        let struct_name = self.name.to_struct_name();
+        quote! { ::proptest::arbitrary::any::<#struct_name>() }
+      },
+      | t => {
+        t.print_strategy().expect("Internal Error: Expected a simple type")
+      },
+    }
+  }
+
+  /// Prints a `proptest::arbitrary::Arbitrary` impl generating values that
+  /// pass validation.
+  fn print_arbitrary(t: &ConvexType, struct_name: &Ident) -> TokenStream {
+    let strategy = match t {
+      | ConvexType::Object(object) => {
+        // Nested pairs avoid the size limit of tuple strategies.
+        let mut strategy = quote! { ::proptest::strategy::Just(()) };
+        let mut pattern = quote! { () };
+        for field in object.fields.iter().rev() {
+          let field_name = field.name.to_field_name();
+          let field_strategy = field.print_strategy();
+          strategy = quote! { (#field_strategy, #strategy) };
+          pattern = quote! { (#field_name, #pattern) };
+        }
+        let field_names = object.fields.iter().map(|f| f.name.to_field_name());
+        let rest = object.rest.as_ref().map(|rest| {
+          let rest = Ident::new(rest, Span::call_site());
+          quote! { #rest: ::std::collections::BTreeMap::new(), }
+        });
+        quote! {
+          ::proptest::strategy::Strategy::prop_map(#strategy, |#pattern| {
+            #struct_name {
+              #( #field_names, )*
+              #rest
+            }
+          })
+        }
+      },
+      | ConvexType::Union(types) => {
+        let branches = types.iter().enumerate().map(|(i, t)| {
+          let branch_name =
+            Ident::new(format!("Variant{}", i + 1).as_str(), Span::call_site());
+          let full_branch_name = Ident::new(
+            format!("{}Variant{}", struct_name, i + 1).as_str(),
+            Span::call_site(),
+          );
+          match (t, t.print_strategy()) {
+            | (ConvexType::Null, _) => quote! {
+              ::proptest::strategy::Just(#struct_name::#branch_name)
+            },
+            | (_, Some(strategy)) => quote! {
+              ::proptest::strategy::Strategy::prop_map(
+                #strategy,
+                #struct_name::#branch_name,
+              )
+            },
+            | (_, None) => quote! {
+              ::proptest::strategy::Strategy::prop_map(
+                ::proptest::arbitrary::any::<#full_branch_name>(),
+                #struct_name::#branch_name,
+              )
+            },
+          }
+        });
+        // Every branch is generated, but values only round-trip when no
+        // earlier branch accepts them since parsing picks the first match.
+        quote! { ::proptest::prop_oneof![#( #branches ),*] }
+      },
+      | _ => panic!("Internal Error: Expected an object or union"),
+    };
+    quote! {
+      #[allow(non_snake_case)]
+      impl ::proptest::arbitrary::Arbitrary for #struct_name {
+        type Parameters = ();
+        type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+        fn arbitrary_with(_args: ()) -> Self::Strategy {
+          ::proptest::strategy::Strategy::boxed(#strategy)
+        }
+      }
+    }
+  }
+
+  /// Prints a fixture expression for the rust type of this field.
+  fn print_fixture_value(&self) -> TokenStream {
+    match &self.t {
+      | ConvexType::Optional(child) | ConvexType::Nullable(child) => {
+        let value = child.print_fixture_value();
+        quote! {
+          if fixtures.some() {
+            ::core::option::Option::Some(#value)
+          } else {
+            ::core::option::Option::None
+          }
+        }
+      },
+      | ConvexType::Array(child) => {
+        let value = child.print_fixture_value();
+        quote! {
+          (0..fixtures.array_len()).map(|_| #value).collect::<::std::vec::Vec<_>>()
+        }
+      },
+      | ConvexType::Record(keys, child) => {
+        let key = keys
+          .print_fixture("key")
+          .expect("Internal Error: Expected a simple type");
+        let value = child.print_fixture_value();
+        quote! {
+          (0..fixtures.array_len())
+            .map(|_| (#key, #value))
+            .collect::<::std::collections::BTreeMap<_, _>>()
+        }
+      },
+      | ConvexType::Object(_) | ConvexType::Union(_) => {
+        let struct_name = self.name.to_struct_name();
+        quote! { #struct_name::fixture_with(fixtures) }
+      },
+      | t => t
+        .print_fixture(&self.name.id)
+        .expect("Internal Error: Expected a simple type"),
+    }
+  }
+
+  /// Prints `fixture` functions creating deterministic sample values.
+  fn print_fixture_fns(t: &ConvexType, struct_name: &Ident) -> TokenStream {
+    let value = match t {
+      | ConvexType::Object(object) => {
+        let fields = object.fields.iter().map(|field| {
+          let field_name = field.name.to_field_name();
+          let value = field.print_fixture_value();
+          quote! { #field_name: #value, }
+        });
+        let rest = object.rest.as_ref().map(|rest| {
+          let rest = Ident::new(rest, Span::call_site());
+          quote! { #rest: ::std::collections::BTreeMap::new(), }
+        });
+        quote! {
+          #struct_name {
+            #( #fields )*
+            #rest
+          }
+        }
+      },
+      | ConvexType::Union(types) => {
+        let count = types.len();
+        let arms = types.iter().enumerate().map(|(i, t)| {
+          let branch_name = Ident::new(
+            format!("Variant{}", i + 1).as_str(),
+            Span::call_site(),
+          );
+          let full_branch_name = Ident::new(
+            format!("{}Variant{}", struct_name, i + 1).as_str(),
+            Span::call_site(),
+          );
+          let value = match (t, t.print_fixture(&struct_name.to_string())) {
+            | (ConvexType::Null, _) => quote! { #struct_name::#branch_name },
+            | (_, Some(value)) => quote! { #struct_name::#branch_name(#value) },
+            | (ConvexType::Array(child), None) => {
+              let value = child.print_fixture_value();
+              quote! {
+                #struct_name::#branch_name(
+                  (0..fixtures.array_len()).map(|_| #value).collect()
+                )
+              }
+            },
+            | (ConvexType::Record(keys, child), None) => {
+              let key = keys
+                .print_fixture("key")
+                .expect("Internal Error: Expected a simple type");
+              let value = child.print_fixture_value();
+              quote! {
+                #struct_name::#branch_name(
+                  (0..fixtures.array_len()).map(|_| (#key, #value)).collect()
+                )
+              }
+            },
+            | (_, None) => quote! {
+              #struct_name::#branch_name(#full_branch_name::fixture_with(fixtures))
+            },
+          };
+          quote! { | #i => #value, }
+        });
+        quote! {
+          match fixtures.pick(#count) {
+            #( #arms )*
+            | _ => unreachable!(),
+          }
+        }
+      },
+      | _ => panic!("Internal Error: Expected an object or union"),
+    };
+    quote! {
+      #[allow(non_snake_case)]
+      impl #struct_name {
+        /// A deterministic sample value, the same `seed` gives the same value.
+        pub fn fixture(seed: u64) -> Self {
+          Self::fixture_with(&mut ::ragkit_convex_runtime::Fixtures::new(seed))
+        }
+
+        pub fn fixture_with(fixtures: &mut ::ragkit_convex_runtime::Fixtures) -> Self {
+          #value
+        }
+      }
+    }
+  }
+
+  /// Prints a `FIELD_<PATH>` constant for every (nested) field path.
+  fn print_field_paths(
+    object: &ConvexObject,
+    struct_name: &Ident,
+  ) -> TokenStream {
+    let consts = object.field_paths().into_iter().map(|(path, field)| {
+      let const_name = to_field_path_const(&path);
+      let path = path.join(".");
+      let field_type = field.print_type();
+      let value = field.t.print_to_convex_value(quote! { value });
+      quote! {
+        pub const #const_name: ::ragkit_convex_runtime::FieldPath<#field_type> =
+          ::ragkit_convex_runtime::FieldPath::new(#path, |value| #value);
+      }
+    });
+    quote! {
+      #[allow(non_snake_case)]
+      impl #struct_name {
+        #( #consts )*
+      }
+    }
+  }
+
+  /// The rust type used for this field in its parent struct.
+  pub fn print_type(&self) -> TokenStream {
+    match &self.t {
+      | ConvexType::Optional(child) | ConvexType::Nullable(child) => {
+        let child_type = child.print_type();
+        quote! { Option<#child_type> }
+      },
+      | ConvexType::Array(child) => {
+        let child_type = child.print_type();
+        quote! { Vec<#child_type> }
+      },
+      | ConvexType::Record(_, child) => {
+        let child_type = child.print_type();
+        quote! { ::std::collections::BTreeMap<String, #child_type> }
+      },
+      | ConvexType::Object(_) | ConvexType::Union(_) => {
+        let struct_name = self.name.to_struct_name();
+        quote! { #struct_name }
+      },
+      | t => t.print().expect("Internal Error: Expected a simple type"),
+    }
+  }
 
-        structs.push(quote! {
-          #ignore_attributes
-          #enum_struct_attributes
-          pub enum #struct_name {
-            #( #enum_kinds )*
-          }
-        });
+  /// Prints the structs and enums for objects and unions within this field.
+  pub fn print_nested(&self) -> Vec<TokenStream> {
+    match &self.t {
+      | ConvexType::Object(_) | ConvexType::Union(_) => self.print(),
+      | ConvexType::Optional(child)
+      | ConvexType::Nullable(child)
+      | ConvexType::Array(child)
+      | ConvexType::Record(_, child) => child.print_nested(),
+      | _ => Vec::new(),
+    }
+  }
 
-        impls.push(quote! {
-          #ignore_attributes
-          impl ::serde::Serialize for #struct_name {
-            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
-            where S: ::serde::Serializer {
-              match *self {
-                #( #serialize_arms )*
-              }
+  /// The names of the structs and enums generated for this field and every
+  /// type nested in it.
+  pub fn struct_names(&self) -> Vec<String> {
+    let mut names = Vec::new();
+    match &self.t {
+      | ConvexType::Object(object) => {
+        names.push(self.name.to_struct_name().to_string());
+        for field in &object.fields {
+          names.append(&mut field.struct_names());
+        }
+      },
+      | ConvexType::Union(types) => {
+        let struct_name = self.name.to_struct_name();
+        names.push(struct_name.to_string());
+        for (i, t) in types.iter().enumerate() {
+          if let ConvexType::Object(object) = t {
+            names.push(format!("{}Variant{}", struct_name, i + 1));
+            for field in &object.fields {
+              names.append(&mut field.struct_names());
             }
           }
-        });
+        }
+      },
+      | ConvexType::Optional(child)
+      | ConvexType::Nullable(child)
+      | ConvexType::Array(child)
+      | ConvexType::Record(_, child) => names.append(&mut child.struct_names()),
+      | _ => {},
+    }
+    names
+  }
 
-        impls.push(quote! {
-          #ignore_attributes
-          impl #struct_name {
-            fn from_convex_value(
-              value: &::convex::Value
-            ) -> ::core::result::Result<Self, ::anyhow::Error> {
-              match value {
-                #( #extract_arms )*
-                | _ => {
-                  Err(::anyhow::anyhow!("Invalid union type for '{}'", #field_name_str))
-                },
-              }
+  /// The name of the patch type, `<Model>Patch` unless a nested type like the
+  /// object of a `patch` field already has it, then `<Model>Patch2` and so on.
+  pub fn patch_name(&self) -> Ident {
+    let struct_names = self.struct_names();
+    let struct_name = self.name.to_struct_name();
+    let mut name = format!("{}Patch", struct_name);
+    let mut i = 1;
+    while struct_names.contains(&name) {
+      i += 1;
+      name = format!("{}Patch{}", struct_name, i);
+    }
+    Ident::new(name.as_str(), Span::call_site())
+  }
+
+  /// Prints a `<Model>Patch` struct where every field is optional and `None`
+  /// means untouched. Optional fields use `Some(None)` to unset them, which
+  /// stays distinct from setting a nullable field to null.
+  ///
+  /// Patches are shallow like `db.patch`, nested objects are replaced as a
+  /// whole. System fields starting with `_` can't be patched and are skipped.
+  fn print_patch(
+    object: &ConvexObject,
+    struct_name: &Ident,
+    patch_name: &Ident,
+  ) -> Vec<TokenStream> {
+    let ignore_attributes = quote! {
+      #[allow(non_snake_case)]
+    };
+    let mut patch_fields = Vec::new();
+    let mut convex_fields = Vec::new();
+    let mut unset_fields = Vec::new();
+    let mut apply_fields = Vec::new();
+    for field in &object.fields {
+      if field.name.id.starts_with('_') {
+        continue;
+      }
+      let field_name = field.name.to_field_name();
+      let field_name_str = field_name.to_string();
+      let field_type = field.print_type();
+      patch_fields.push(quote! {
+        pub #field_name: ::core::option::Option<#field_type>,
+      });
+      apply_fields.push(quote! {
+        if let ::core::option::Option::Some(value) = &self.#field_name {
+          model.#field_name = value.clone();
+        }
+      });
+      match &field.t {
+        // Convex can't send `undefined` and null is a valid value of
+        // nullable fields, so unset fields are listed by name instead.
+        | ConvexType::Optional(child) => {
+          let convex_value = child.t.print_to_convex_value(quote! { value });
+          convex_fields.push(quote! {
+            if let ::core::option::Option::Some(::core::option::Option::Some(value)) = &self.#field_name {
+              object.insert(#field_name_str.into(), #convex_value);
+            }
+          });
+          unset_fields.push(quote! {
+            if let ::core::option::Option::Some(::core::option::Option::None) = &self.#field_name {
+              unset.push(::convex::Value::String(#field_name_str.into()));
+            }
+          });
+        },
+        | t => {
+          let convex_value = t.print_to_convex_value(quote! { value });
+          convex_fields.push(quote! {
+            if let ::core::option::Option::Some(value) = &self.#field_name {
+              object.insert(#field_name_str.into(), #convex_value);
             }
+          });
+        },
+      }
+    }
 
-            #(
-              #as_fns
-            )*
+    vec![
+      quote! {
+        #ignore_attributes
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct #patch_name {
+          #( #patch_fields )*
+        }
+      },
+      quote! {
+        #ignore_attributes
+        impl #patch_name {
+          /// Prints `{ set: { ... }, unset: ["field", ...] }`, the mutation
+          /// passes `set` to `db.patch` with every `unset` field as
+          /// `undefined`.
+          pub fn to_convex_value(&self) -> ::convex::Value {
+            let mut object = ::std::collections::BTreeMap::new();
+            #( #convex_fields )*
+            let mut unset = ::std::vec::Vec::new();
+            #( #unset_fields )*
+            ::convex::Value::Object(::std::collections::BTreeMap::from([
+              (::std::string::String::from("set"), ::convex::Value::Object(object)),
+              (::std::string::String::from("unset"), ::convex::Value::Array(unset)),
+            ]))
           }
-        });
 
-        impls.push(quote! {
-          #ignore_attributes
-          impl ::core::convert::From<#struct_name> for ::serde_json::Value {
-            fn from(value: #struct_name) -> Self {
-              match value {
-                #( #json_arms )*
-              }
-            }
+          pub fn apply(&self, model: &mut #struct_name) {
+            #( #apply_fields )*
           }
-        })
-      },
-      | _ => {
-        panic!(
-          "Internal Error: Should have a complex field like object or union"
-        )
+        }
       },
-    }
-
-    [structs, impls].concat()
+    ]
   }
 
   fn print_from_convex_value(
-    fields: &Vec<ConvexField>,
+    object: &ConvexObject,
     struct_name: &Ident,
   ) -> TokenStream {
     let ignore_attributes = quote! {
       #[allow(non_snake_case)]
     };
-    let extract_ts = Self::print_extract_fields(fields, struct_name);
+    let extract_ts = Self::print_extract_fields(object, struct_name);
     quote! {
       #ignore_attributes
       impl #struct_name {
-        fn from_convex_value(
+        pub fn from_convex_value(
           value: &::convex::Value
         ) -> ::core::result::Result<Self, ::anyhow::Error> {
           #extract_ts
@@ -375,44 +1813,167 @@ impl ConvexField {
     }
   }
 
+  fn print_to_convex_value(
+    object: &ConvexObject,
+    struct_name: &Ident,
+  ) -> TokenStream {
+    let ignore_attributes = quote! {
+      #[allow(non_snake_case)]
+    };
+    // Declared fields always win over rest.
+    let init_object = match &object.rest {
+      | Some(rest) => {
+        let rest = Ident::new(rest, Span::call_site());
+        let declared_fields = object.print_declared_fields();
+        quote! {
+          #declared_fields
+          let mut object: ::std::collections::BTreeMap<String, ::convex::Value> = self
+            .#rest
+            .iter()
+            .filter(|(key, _)| !declared_fields.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        }
+      },
+      | None => quote! {
+        let mut object = ::std::collections::BTreeMap::new();
+      },
+    };
+    let mut convex_fields = Vec::new();
+    for field in &object.fields {
+      let field_name = field.name.to_field_name();
+      let field_name_str = field_name.to_string();
+      match &field.t {
+        // Missing fields are represented by omitting the key.
+        | ConvexType::Optional(child) => {
+          let convex_value = child.t.print_to_convex_value(quote! { value });
+          convex_fields.push(quote! {
+            if let ::core::option::Option::Some(value) = &self.#field_name {
+              object.insert(#field_name_str.into(), #convex_value);
+            }
+          });
+        },
+        | t => {
+          let convex_value =
+            t.print_to_convex_value(quote! { &self.#field_name });
+          convex_fields.push(quote! {
+            object.insert(#field_name_str.into(), #convex_value);
+          });
+        },
+      }
+    }
+    quote! {
+      #ignore_attributes
+      impl #struct_name {
+        pub fn to_convex_value(&self) -> ::convex::Value {
+          #init_object
+          #( #convex_fields )*
+          ::convex::Value::Object(object)
+        }
+      }
+    }
+  }
+
   fn print_structs(
-    fields: &Vec<ConvexField>,
+    object: &ConvexObject,
     struct_name: &Ident,
   ) -> Vec<TokenStream> {
     let ignore_attributes = quote! {
       #[allow(non_snake_case)]
     };
+    // Note: We need a custom serialize to control how numbers are printed.
     let struct_attributes = quote! {
-      #[derive(::serde::Serialize, ::serde::Deserialize, Clone, Debug, PartialEq)]
+      #[derive(::serde::Deserialize, Clone, Debug, PartialEq)]
     };
     let mut structs = Vec::new();
     let mut rendered_fields = Vec::new();
-    for field in fields {
+    let mut serialize_entries = Vec::new();
+    let mut optional_counts = Vec::new();
+    for field in &object.fields {
       let field_name = field.name.to_field_name();
-      match field.t.print() {
-        | Some(field_type) => rendered_fields.push(quote! {
-          pub #field_name: #field_type,
-        }),
-        | None => {
-          // TODO: This is hacky hard-codes nesting
-          if let ConvexType::Optional(child) = &field.t {
-            let struct_name = field.name.to_struct_name();
-            let field_type = quote! { Option<#struct_name> };
-            let mut child_struct = child.print();
-            structs.append(&mut child_struct);
-            rendered_fields.push(quote! {
-              pub #field_name: #field_type,
-            });
-          } else {
-            let field_type = field.name.to_struct_name();
-            let mut child_struct = field.print();
-            structs.append(&mut child_struct);
-            rendered_fields.push(quote! {
-              pub #field_name: #field_type,
-            });
+      let field_name_str = field_name.to_string();
+      match &field.t {
+        // Missing fields are represented by omitting the key.
+        | ConvexType::Optional(child) => {
+          let json_value = child.t.print_to_json_value(quote! { value });
+          serialize_entries.push(quote! {
+            if let ::core::option::Option::Some(value) = &self.#field_name {
+              map.serialize_entry(#field_name_str, &#json_value)?;
+            }
+          });
+          optional_counts.push(quote! {
+            + usize::from(self.#field_name.is_some())
+          });
+        },
+        | t => {
+          let json_value = t.print_to_json_value(quote! { &self.#field_name });
+          serialize_entries.push(quote! {
+            map.serialize_entry(#field_name_str, &#json_value)?;
+          });
+        },
+      }
+      let field_type = field.print_type();
+      // An optional nullable field keeps null (`Some(None)`) apart from a
+      // missing key (`None`) when deserializing, and numbers accept the
+      // `{"$float": ...}` encoding they may be serialized with.
+      let deserialize_with = match &field.t {
+        | ConvexType::Optional(child) => {
+          match (&child.t, child.t.contains_float()) {
+            | (ConvexType::Nullable(_), true) => {
+              Some("::ragkit_convex_runtime::deserialize_some_float")
+            },
+            | (ConvexType::Nullable(_), false) => {
+              Some("::ragkit_convex_runtime::deserialize_some")
+            },
+            | (_, true) => Some("::ragkit_convex_runtime::deserialize_float"),
+            | (_, false) => None,
           }
         },
+        | t if t.contains_float() => {
+          Some("::ragkit_convex_runtime::deserialize_float")
+        },
+        | _ => None,
+      };
+      let deserialize_attribute = match deserialize_with {
+        | Some(path) if matches!(field.t, ConvexType::Optional(_)) => quote! {
+          #[serde(default, deserialize_with = #path)]
+        },
+        | Some(path) => quote! {
+          #[serde(deserialize_with = #path)]
+        },
+        | None => quote! {},
+      };
+      rendered_fields.push(quote! {
+        #deserialize_attribute
+        pub #field_name: #field_type,
+      });
+      structs.append(&mut field.print_nested());
+    }
+    let mut rest_count = quote! { 0 };
+    if let Some(rest) = &object.rest {
+      let rest = Ident::new(rest, Span::call_site());
+      let declared_fields = object.print_declared_fields();
+      // Note: convex::Value does not implement Deserialize, undeclared keys
+      // are decoded from Convex's JSON encoding instead.
+      rendered_fields.push(quote! {
+        #[serde(flatten, deserialize_with = "::ragkit_convex_runtime::deserialize_rest")]
+        pub #rest: ::std::collections::BTreeMap<String, ::convex::Value>,
+      });
+      // Keys shadowed by a declared field are skipped below.
+      rest_count = quote! {
+        {
+          #declared_fields
+          self.#rest.keys().filter(|key| !declared_fields.contains(&key.as_str())).count()
+        }
       };
+      serialize_entries.push(quote! {
+        #declared_fields
+        for (key, value) in self.#rest.iter() {
+          if !declared_fields.contains(&key.as_str()) {
+            map.serialize_entry(key, &::serde_json::Value::from(value.clone()))?;
+          }
+        }
+      });
     }
     structs.push(quote! {
       #ignore_attributes
@@ -421,43 +1982,50 @@ impl ConvexField {
         #( #rendered_fields )*
       }
     });
+    structs.push(Self::print_constructors(object, struct_name));
+    let field_count = object
+      .fields
+      .iter()
+      .filter(|field| !matches!(field.t, ConvexType::Optional(_)))
+      .count();
+    structs.push(quote! {
+      #ignore_attributes
+      impl ::serde::Serialize for #struct_name {
+        fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+        where S: ::serde::Serializer {
+          use ::serde::ser::SerializeMap;
+          let mut map = serializer.serialize_map(::core::option::Option::Some(
+            #field_count #( #optional_counts )* + #rest_count
+          ))?;
+          #( #serialize_entries )*
+          map.end()
+        }
+      }
+    });
     structs
   }
 
-  fn print_to_json_impl(
-    fields: &Vec<ConvexField>,
-    struct_name: &Ident,
-  ) -> TokenStream {
+  fn print_to_json_impl(struct_name: &Ident) -> TokenStream {
     let ignore_attributes = quote! {
       #[allow(non_snake_case)]
     };
-    let mut json_fields = Vec::new();
-    for field in fields {
-      let field_name = field.name.to_field_name();
-      let field_name_str = field_name.to_string();
-      json_fields.push(quote! {
-        #field_name_str: value.#field_name,
-      });
-    }
     quote! {
       #ignore_attributes
       impl ::core::convert::From<#struct_name> for ::serde_json::Value {
         fn from(value: #struct_name) -> Self {
-          ::serde_json::json!({
-            #( #json_fields )*
-          })
+          ::serde_json::json!(value)
         }
       }
     }
   }
 
   fn print_extract_fields(
-    fields: &Vec<ConvexField>,
+    object: &ConvexObject,
     struct_name: &Ident,
   ) -> TokenStream {
     let mut extract_fields = Vec::new();
     let mut field_idents = Vec::new();
-    for field in fields {
+    for field in &object.fields {
       extract_fields.push(Self::print_extract_field(field, None));
       let field_name = field.name.to_field_name();
       field_idents.push(quote! {
@@ -465,9 +2033,47 @@ impl ConvexField {
       });
     }
 
+    let declared_fields = object.print_declared_fields();
+    if let Some(rest) = &object.rest {
+      let rest = Ident::new(rest, Span::call_site());
+      extract_fields.push(quote! {
+        #declared_fields
+        let #rest = object
+          .iter()
+          .filter(|(key, _)| !declared_fields.contains(&key.as_str()))
+          .map(|(key, value)| (key.clone(), value.clone()))
+          .collect();
+      });
+      field_idents.push(quote! {
+        #rest,
+      });
+    }
+
+    let check_unknown_fields = if object.strict {
+      let struct_name_str = struct_name.to_string();
+      quote! {
+        #declared_fields
+        let unknown_fields: ::std::vec::Vec<&str> = object
+          .keys()
+          .map(|key| key.as_str())
+          .filter(|key| !declared_fields.contains(key))
+          .collect();
+        if !unknown_fields.is_empty() {
+          return Err(::anyhow::anyhow!(
+            "Unexpected fields in '{}': {}",
+            #struct_name_str,
+            unknown_fields.join(", "),
+          ));
+        }
+      }
+    } else {
+      quote! {}
+    };
+
     quote! {
       match value {
         | ::convex::Value::Object(object) => {
+          #check_unknown_fields
           #( #extract_fields )*
 
           Ok(#struct_name {
@@ -481,7 +2087,7 @@ impl ConvexField {
     }
   }
 
-  fn print_extract_field(
+  pub fn print_extract_field(
     field: &ConvexField,
     match_ident: Option<Ident>,
   ) -> TokenStream {
@@ -556,6 +2162,12 @@ impl ConvexField {
         error_name,
         *literal,
       ),
+      | ConvexType::FloatLiteral(literal) => Self::print_extract_float_literal(
+        ident,
+        match_target,
+        error_name,
+        *literal,
+      ),
       | ConvexType::BoolLiteral(literal) => Self::print_extract_bool_literal(
         ident,
         match_target,
@@ -571,12 +2183,54 @@ impl ConvexField {
         )
       },
 
+      | ConvexType::Array(next_t) => {
+        let next_target = Ident::new("value", Span::call_site());
+        let child_match = Self::print_extract_field(next_t, Some(next_target));
+        let child_ident = next_t.name.to_field_name();
+        quote! {
+          let #ident = match #match_target {
+            | ::core::option::Option::Some(::convex::Value::Array(values)) => {
+              let mut items = ::std::vec::Vec::with_capacity(values.len());
+              for value in values.iter() {
+                let value = ::core::option::Option::Some(value);
+                #child_match
+                items.push(#child_ident);
+              }
+              items
+            },
+            | _ => {
+              return Err(::anyhow::anyhow!("Expected '{}' to be an array", #error_name));
+            },
+          };
+        }
+      },
+      | ConvexType::Record(_, next_t) => {
+        let next_target = Ident::new("value", Span::call_site());
+        let child_match = Self::print_extract_field(next_t, Some(next_target));
+        let child_ident = next_t.name.to_field_name();
+        quote! {
+          let #ident = match #match_target {
+            | ::core::option::Option::Some(::convex::Value::Object(values)) => {
+              let mut items = ::std::collections::BTreeMap::new();
+              for (key, value) in values.iter() {
+                let value = ::core::option::Option::Some(value);
+                #child_match
+                items.insert(key.clone(), #child_ident);
+              }
+              items
+            },
+            | _ => {
+              return Err(::anyhow::anyhow!("Expected '{}' to be a record", #error_name));
+            },
+          };
+        }
+      },
       | ConvexType::Optional(next_t) => {
         let next_target = Ident::new("value", Span::call_site());
         let child_match = Self::print_extract_field(next_t, Some(next_target));
         quote! {
+          // Explicit nulls are only accepted when the child type allows them.
           let #ident = match #match_target {
-            | ::core::option::Option::Some(::convex::Value::Null) => ::core::option::Option::None,
             | ::core::option::Option::None => ::core::option::Option::None,
             | value => {
               #child_match
@@ -585,6 +2239,20 @@ impl ConvexField {
           };
         }
       },
+      | ConvexType::Nullable(next_t) => {
+        let next_target = Ident::new("value", Span::call_site());
+        let child_match = Self::print_extract_field(next_t, Some(next_target));
+        let child_ident = next_t.name.to_field_name();
+        quote! {
+          let #ident = match #match_target {
+            | ::core::option::Option::Some(::convex::Value::Null) => ::core::option::Option::None,
+            | value => {
+              #child_match
+              ::core::option::Option::Some(#child_ident)
+            },
+          };
+        }
+      },
       | _ => {
         panic!("Unimplemented print_extract_type")
       },
@@ -653,18 +2321,50 @@ impl ConvexField {
     }
   }
 
+  fn print_extract_float_literal(
+    ident: Ident,
+    match_target: TokenStream,
+    error_name: String,
+    literal: f64,
+  ) -> TokenStream {
+    // Floats match exactly, ints only when they convert to the same value.
+    quote! {
+      let #ident = match #match_target {
+        | ::core::option::Option::Some(::convex::Value::Float64(value)) => {
+          let v = value.clone();
+          if v != #literal {
+            return Err(::anyhow::anyhow!("Expected '{}' to be the float literal '{:?}'", #error_name, #literal));
+          } else {
+            v
+          }
+        },
+        | ::core::option::Option::Some(::convex::Value::Int64(value)) => {
+          let v = value.clone() as f64;
+          if v != #literal {
+            return Err(::anyhow::anyhow!("Expected '{}' to be the float literal '{:?}'", #error_name, #literal));
+          } else {
+            v
+          }
+        },
+        | _ => {
+          return Err(::anyhow::anyhow!("Expected '{}' to be a float literal", #error_name));
+        },
+      };
+    }
+  }
+
   fn print_extract_int_literal(
     ident: Ident,
     match_target: TokenStream,
     error_name: String,
     literal: i64,
   ) -> TokenStream {
-    // TODO: Should this support both float and int?
+    // Floats only match when they are whole, `-1.9` is not `-1`.
     quote! {
       let #ident = match #match_target {
         | ::core::option::Option::Some(::convex::Value::Float64(value)) => {
           let v = value.clone() as i64;
-          if v != #literal {
+          if value.fract() != 0.0 || v != #literal {
             return Err(::anyhow::anyhow!("Expected '{}' to be the int literal '{}'", #error_name, #literal));
           } else {
             v
@@ -748,133 +2448,67 @@ impl ConvexField {
     }
   }
 
-  fn parse_child(name: &ConvexName, input: ParseStream) -> Result<Self> {
-    // name: v.string(...)
-    // ^^^^
-    let ident = Ident::parse(input)?;
-    let id = ident.to_string();
-    let name = ConvexName { path: name.full_path(), id };
-
-    // name: v.string(...)
-    //     ^
-    let _ = input.parse::<Token![:]>()?;
-
-    // name: v.string(...)
-    //       ^^^^^^^^^^^^^
-    let t = Self::parse_validator_call(&name, input)?;
-
-    Ok(Self { name, t })
-  }
-
-  fn parse_validator_call(
+  pub fn parse_validator_call(
     name: &ConvexName,
     input: ParseStream,
   ) -> Result<ConvexType> {
-    // v.string(...)
-    // ^
-    let v = Ident::parse(input)?;
-    if v != "v" {
-      return Err(Error::new_spanned(&v, "Expected v.method()"));
-    }
-
-    // v.string(...)
-    //  ^
-    let _ = input.parse::<Token![.]>()?;
-
-    // v.string(...)
-    //   ^^^^^^
-    let method_ident = Ident::parse(input)?;
-    let method = method_ident.to_string();
-
-    // v.string(...)
-    //         ^^^^^
-    let inner;
-    let _ = syn::parenthesized!(inner in input);
-
-    match method.as_str() {
-      | "id" => {
-        let lit = Lit::parse(&inner)?;
-        match lit.clone() {
-          | Lit::Str(str_lit) => Ok(ConvexType::Id(str_lit.value())),
-          | _ => Err(Error::new_spanned(&lit, "Expected string literal")),
-        }
-      },
-      | "null" => Ok(ConvexType::Null),
-      | "int64" => Ok(ConvexType::Int64),
-      | "number" => Ok(ConvexType::Number),
-      | "boolean" => Ok(ConvexType::Bool),
-      | "string" => Ok(ConvexType::String),
-
-      | "literal" => {
-        let lit = Lit::parse(&inner)?;
-        match lit.clone() {
-          | Lit::Str(s) => Ok(ConvexType::StringLiteral(s.value())),
-          | Lit::Bool(b) => Ok(ConvexType::BoolLiteral(b.value())),
-          | Lit::Int(i) => Ok(ConvexType::IntLiteral(i.base10_parse::<i64>()?)),
-          | _ => Err(Error::new_spanned(&lit, "Unsupported literal")),
-        }
-      },
-
-      | "optional" => {
-        let t = Self::parse_validator_call(name, &inner)?;
-        Ok(ConvexType::Optional(Box::new(ConvexField {
-          name: name.clone(),
-          t,
-        })))
-      },
-
-      | "object" => {
-        let object_inner;
-        let _ = syn::braced!(object_inner in inner);
-        let ts =
-          Self::parse_comma_separated(name, &object_inner, |name, b| {
-            Self::parse_child(name, b)
-          })?;
-        Ok(ConvexType::Object(ts))
-      },
-
-      | "union" => {
-        let ts = Self::parse_comma_separated(name, &inner, |name, b| {
-          Self::parse_validator_call(name, b)
-        })?;
-        if ts.len() < 2 {
-          return Err(Error::new_spanned(
-            &method_ident,
-            "Unions must have 2 or more branches",
-          ));
-        }
-        Ok(ConvexType::Union(ts))
-      },
-
-      | _ => {
-        Err(Error::new_spanned(&method_ident, "Unsupported validator call"))
-      },
-    }
+    let span = input.span();
+    let validator = parse::parse_validator(input)?;
+    let t = ConvexType::from_validator(name, validator);
+    t.check_field_paths(span)?;
+    Ok(t)
   }
 
-  fn parse_comma_separated<T>(
+  pub fn parse_object(
     name: &ConvexName,
     buffer: &ParseBuffer,
-    f: fn(&ConvexName, &ParseBuffer) -> Result<T>,
-  ) -> Result<Vec<T>> {
-    let mut results = Vec::new();
-    let mut first = true;
-    let mut comma_token = Ok(());
-    while !buffer.is_empty() {
-      if !first {
-        // Must have comma token if this wasn't the first item.
-        comma_token?;
-      }
-      let x = f(name, buffer)?;
-      results.push(x);
-      comma_token = buffer.parse::<Token![,]>().map(|_| ());
-      first = false;
+    strict: bool,
+  ) -> Result<ConvexObject> {
+    let span = buffer.span();
+    let object = parse::parse_object(buffer, strict)?;
+    let object = ConvexObject::from_validator(name, object);
+    object.check_field_paths(span)?;
+    Ok(object)
+  }
+}
+
+/// Converts a literal like `"github"` into a function name, if possible.
+fn to_fn_name(s: &str) -> Option<Ident> {
+  let name: String = s
+    .chars()
+    .map(
+      |c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' },
+    )
+    .collect();
+  syn::parse_str::<Ident>(&name).ok()
+}
+
+/// Names the constant for a field path like `platform.username`, which is
+/// `FIELD_PLATFORM_USERNAME`.
+fn to_field_path_const(path: &[String]) -> Ident {
+  let name = path
+    .iter()
+    .map(|part| to_screaming_snake_case(part))
+    .collect::<Vec<_>>()
+    .join("_");
+  Ident::new(format!("FIELD_{}", name).as_str(), Span::call_site())
+}
+
+/// Converts a field name like `firstName` into `FIRST_NAME`.
+fn to_screaming_snake_case(s: &str) -> String {
+  let mut out = String::new();
+  let mut previous_lowercase = false;
+  for c in s.chars() {
+    if c.is_uppercase() && previous_lowercase {
+      out.push('_');
     }
-    Ok(results)
+    previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+    out.extend(c.to_uppercase());
   }
+  out
 }
 
-fn capitalize_first_char(s: &str) -> String {
+pub fn capitalize_first_char(s: &str) -> String {
   s.char_indices().fold(String::new(), |mut acc, (i, c)| {
     if i == 0 {
       acc.extend(c.to_uppercase());
@@ -0,0 +1,76 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use ragkit_convex_macros::convex_model;
+
+convex_model!(#[strict] User {
+  _id: v.id("users"),
+  name: v.string(),
+  age: v.optional(v.int64()),
+  score: v.number(),
+  bio: v.nullable(v.string()),
+  tags: v.array(v.string()),
+  counts: v.record(v.string(), v.int64()),
+  platform: v.union(
+    v.object({
+      platform: v.literal("github"),
+      username: v.string(),
+      stars: v.array(v.int64()),
+    }),
+    v.object({
+      platform: v.literal("google"),
+      verified: v.literal(true),
+    }),
+    v.null(),
+  ),
+  settings: v.object({
+    theme: v.union(v.literal("dark"), v.literal("light")),
+    ..rest
+  }),
+});
+
+proptest! {
+  // The union branches above are told apart by their literals, values of
+  // ambiguous unions like v.union(v.string(), v.literal("a")) parse as the
+  // first matching branch and don't round-trip.
+  #[test]
+  fn convex_value_round_trip(user: User) {
+    let value = user.to_convex_value();
+    prop_assert!(User::convex_schema().validate(&value).is_ok());
+    prop_assert_eq!(user, User::from_convex_value(&value).unwrap());
+  }
+
+  #[test]
+  fn literals_and_ids(user: User) {
+    prop_assert_eq!(32, user._id.len());
+    if let UserPlatform::Variant2(google) = &user.platform {
+      prop_assert_eq!("google", google.platform.as_str());
+      prop_assert!(google.verified);
+    }
+  }
+}
+
+#[test]
+fn github_platform_round_trip() {
+  let user = User {
+    _id: "a0aa0000000a0a000aaa0aa0a0a000aa".into(),
+    name: "".into(),
+    age: None,
+    score: 0.0,
+    bio: None,
+    tags: vec![],
+    counts: Default::default(),
+    platform: UserPlatform::Variant1(UserPlatformVariant1 {
+      platform: "github".into(),
+      username: "".into(),
+      stars: vec![],
+    }),
+    settings: UserSettings {
+      theme: UserSettingsTheme::Variant1("dark".into()),
+      rest: Default::default(),
+    },
+  };
+  let value = user.to_convex_value();
+  assert!(User::convex_schema().validate(&value).is_ok());
+  assert_eq!(user, User::from_convex_value(&value).unwrap());
+}
@@ -0,0 +1,99 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use serde_json::json;
+
+#[test]
+fn basic_array() {
+  convex_model!(Model { a: v.array(v.string()) });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::Array(vec![
+      Value::String("apple".into()),
+      Value::String("banana".into()),
+    ]),
+  });
+  let json_data = json!({
+    "a": ["apple", "banana"],
+  });
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(vec!["apple", "banana"], model.a);
+  assert_eq!(json_data, json!(model));
+  assert_eq!(convex_data, model.to_convex_value());
+}
+
+#[test]
+fn basic_array_negative() {
+  convex_model!(Model { a: v.array(v.string()) });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+  }));
+  assert!(model.is_err());
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Array(vec![Value::Int64(42)]),
+  }));
+  assert!(model.is_err());
+}
+
+#[test]
+fn array_of_objects() {
+  convex_model!(Model {
+    a: v.array(v.object({
+      b: v.number(),
+      c: v.optional(v.array(v.union(v.string(), v.null()))),
+    })),
+  });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::Array(vec![
+      Value::Object(btreemap! {
+        "b".into() => Value::Float64(f64::NAN),
+      }),
+      Value::Object(btreemap! {
+        "b".into() => Value::Float64(1.5),
+        "c".into() => Value::Array(vec![
+          Value::String("cherry".into()),
+          Value::Null,
+        ]),
+      }),
+    ]),
+  });
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(2, model.a.len());
+  assert!(model.a[0].b.is_nan());
+  assert_eq!(
+    Some(vec![ModelAC::Variant1("cherry".into()), ModelAC::Variant2]),
+    model.a[1].c,
+  );
+  assert_eq!(convex_data, model.to_convex_value());
+  assert_eq!(
+    serde_json::Value::from(Value::Float64(f64::NAN)),
+    json!(model)["a"][0]["b"],
+  );
+}
+
+#[test]
+fn array_in_union() {
+  convex_model!(Model {
+    a: v.union(v.array(v.int64()), v.array(v.string()), v.null()),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Array(vec![Value::String("apple".into())]),
+  }));
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(ModelA::Variant2(vec!["apple".into()]), model.a);
+  assert_eq!(json!({ "a": ["apple"] }), json!(model));
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Array(vec![Value::Boolean(true)]),
+  }));
+  assert!(model.is_err());
+}
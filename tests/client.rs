@@ -0,0 +1,174 @@
+#![cfg(feature = "client")]
+
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use convex::{ConvexError, FunctionResult, Value};
+use futures::executor::block_on;
+use futures::{stream, StreamExt};
+use maplit::btreemap;
+use ragkit_convex_macros::convex_function;
+use ragkit_convex_runtime::{FunctionClient, FunctionError};
+
+convex_function!(query "messages:list" {
+  args: { channel: v.id("channels") },
+  returns: v.array(v.object({
+    channel: v.id("channels"),
+    body: v.string(),
+  })),
+});
+
+convex_function!(mutation "messages:send" {
+  args: {
+    channel: v.id("channels"),
+    body: v.string(),
+  },
+  returns: v.null(),
+});
+
+convex_function!(action "messages:summarize" {
+  args: { channel: v.id("channels") },
+  returns: v.string(),
+});
+
+// An in-memory stand-in for a deployment.
+#[derive(Default)]
+struct LocalBackend {
+  messages: Vec<(String, String)>,
+}
+
+impl LocalBackend {
+  fn run(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> anyhow::Result<FunctionResult> {
+    let channel = args.get("channel").cloned();
+    match (name, channel) {
+      | (_, Some(Value::String(channel))) if channel == "missing" => {
+        Ok(FunctionResult::ConvexError(ConvexError {
+          message: "Channel not found".into(),
+          data: Value::String("NOT_FOUND".into()),
+        }))
+      },
+      | (MessagesList::PATH, Some(Value::String(channel))) => {
+        Ok(FunctionResult::Value(Value::Array(
+          self
+            .messages
+            .iter()
+            .filter(|(c, _)| *c == channel)
+            .map(|(c, body)| {
+              Value::Object(btreemap! {
+                "channel".into() => Value::String(c.clone()),
+                "body".into() => Value::String(body.clone()),
+              })
+            })
+            .collect(),
+        )))
+      },
+      | (MessagesSend::PATH, Some(Value::String(channel))) => {
+        let Some(Value::String(body)) = args.get("body") else {
+          return Ok(FunctionResult::ErrorMessage("Missing body".into()));
+        };
+        self.messages.push((channel, body.clone()));
+        Ok(FunctionResult::Value(Value::Null))
+      },
+      // Returns the wrong type to check decoding errors.
+      | (MessagesSummarize::PATH, _) => Ok(FunctionResult::Value(Value::Null)),
+      | _ => Err(anyhow::anyhow!("Unknown function {}", name)),
+    }
+  }
+}
+
+impl FunctionClient for LocalBackend {
+  fn query(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    let result = self.run(name, args);
+    async move { result }
+  }
+
+  fn mutation(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    let result = self.run(name, args);
+    async move { result }
+  }
+
+  fn action(
+    &mut self,
+    name: &str,
+    args: BTreeMap<String, Value>,
+  ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send {
+    let result = self.run(name, args);
+    async move { result }
+  }
+}
+
+#[test]
+fn call_functions() {
+  let mut client = LocalBackend::default();
+
+  block_on(MessagesSend::call(
+    &mut client,
+    MessagesSendArgs::new("general".into(), "Hello".into()),
+  ))
+  .expect("send should succeed");
+
+  let messages = block_on(MessagesList::call(
+    &mut client,
+    MessagesListArgs::new("general".into()),
+  ))
+  .expect("list should succeed");
+  assert_eq!(
+    vec![MessagesListReturns::new("general".into(), "Hello".into())],
+    messages,
+  );
+}
+
+#[test]
+fn call_errors() {
+  let mut client = LocalBackend::default();
+
+  let result = block_on(MessagesList::call(
+    &mut client,
+    MessagesListArgs::new("missing".into()),
+  ));
+  let Err(FunctionError::Convex(error)) = result else {
+    panic!("Expected a ConvexError");
+  };
+  assert_eq!(Value::String("NOT_FOUND".into()), error.data);
+
+  let result = block_on(MessagesSummarize::call(
+    &mut client,
+    MessagesSummarizeArgs::new("general".into()),
+  ));
+  assert!(matches!(result, Err(FunctionError::Decode(_))));
+}
+
+#[test]
+fn subscription_updates() {
+  let updates = stream::iter(vec![
+    FunctionResult::Value(Value::Array(vec![])),
+    FunctionResult::Value(Value::Null),
+    FunctionResult::ErrorMessage("Server error".into()),
+    FunctionResult::Value(Value::Array(vec![Value::Object(btreemap! {
+      "channel".into() => Value::String("general".into()),
+      "body".into() => Value::String("Hello".into()),
+    })])),
+  ]);
+
+  let results: Vec<_> = block_on(MessagesList::subscription(updates).collect());
+  assert_eq!(4, results.len());
+  assert!(matches!(&results[0], Ok(messages) if messages.is_empty()));
+  assert!(matches!(&results[1], Err(FunctionError::Decode(_))));
+  assert!(matches!(&results[2], Err(FunctionError::Message(_))));
+  assert!(matches!(
+    &results[3],
+    Ok(messages) if messages[0].body == "Hello"
+  ));
+}
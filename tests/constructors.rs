@@ -0,0 +1,104 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+
+convex_model!(User {
+  _id: v.id("users"),
+  kind: v.literal("user"),
+  name: v.string(),
+  age: v.optional(v.int64()),
+  platform: v.union(
+    v.object({
+      platform: v.literal("google"),
+      verified: v.boolean(),
+    }),
+    v.object({
+      platform: v.literal("github"),
+      username: v.string(),
+    }),
+    v.object({
+      platform: v.literal("as_1"),
+    }),
+  ),
+  status: v.union(v.literal("active"), v.literal("not active"), v.string()),
+});
+
+#[test]
+fn struct_constructor() {
+  let user = User::new(
+    "1234".into(),
+    "Alice".into(),
+    UserPlatform::github("alicecodes".into()),
+    UserStatus::active(),
+  );
+  assert_eq!("user", user.kind);
+  assert_eq!(None, user.age);
+
+  let user = user.with_age(Some(42));
+  assert_eq!(Some(42), user.age);
+
+  let parsed = User::from_convex_value(&Value::Object(btreemap! {
+    "_id".into() => Value::String("1234".into()),
+    "kind".into() => Value::String("user".into()),
+    "name".into() => Value::String("Alice".into()),
+    "age".into() => Value::Int64(42),
+    "platform".into() => Value::Object(btreemap! {
+      "platform".into() => Value::String("github".into()),
+      "username".into() => Value::String("alicecodes".into()),
+    }),
+    "status".into() => Value::String("active".into()),
+  }))
+  .expect("it should parse");
+  assert_eq!(parsed, user);
+}
+
+#[test]
+fn union_constructors() {
+  assert_eq!(
+    UserPlatform::Variant1(UserPlatformVariant1 {
+      platform: "google".into(),
+      verified: true,
+    }),
+    UserPlatform::google(true),
+  );
+  assert_eq!(
+    UserPlatform::Variant2(UserPlatformVariant2 {
+      platform: "github".into(),
+      username: "alicecodes".into(),
+    }),
+    UserPlatform::github("alicecodes".into()),
+  );
+  // Literals that clash with other methods fall back to the variant number.
+  assert_eq!(
+    UserPlatform::Variant3(UserPlatformVariant3 { platform: "as_1".into() }),
+    UserPlatform::variant_3(),
+  );
+
+  assert_eq!(UserStatus::Variant1("active".into()), UserStatus::active());
+  assert_eq!(
+    UserStatus::Variant2("not active".into()),
+    UserStatus::not_active()
+  );
+}
+
+#[test]
+fn constructors_avoid_generated_methods() {
+  convex_model!(Model {
+    kind: v.union(
+      v.literal("fixture"),
+      v.literal("json_schema"),
+      v.literal("from_convex_value"),
+      v.literal("as_1"),
+      v.literal("other"),
+    ),
+  });
+
+  assert_eq!(ModelKind::Variant1("fixture".into()), ModelKind::variant_1());
+  assert_eq!(ModelKind::Variant2("json_schema".into()), ModelKind::variant_2());
+  assert_eq!(
+    ModelKind::Variant3("from_convex_value".into()),
+    ModelKind::variant_3()
+  );
+  assert_eq!(ModelKind::Variant4("as_1".into()), ModelKind::variant_4());
+  assert_eq!(ModelKind::Variant5("other".into()), ModelKind::other());
+}
@@ -0,0 +1,170 @@
+use ragkit_convex_runtime::{
+  diff,
+  ChangeKind,
+  Compatibility,
+  Literal,
+  SchemaChange,
+  Validator,
+};
+use serde_json::json;
+
+fn parse(source: &str) -> Validator {
+  Validator::parse(source).expect("it should parse")
+}
+
+#[test]
+fn diff_fields() {
+  let old = parse(
+    r#"v.object({
+      name: v.string(),
+      age: v.int64(),
+      email: v.optional(v.string()),
+      nickname: v.optional(v.string()),
+      owner: v.id("users"),
+    })"#,
+  );
+  let new = parse(
+    r#"v.object({
+      name: v.string(),
+      age: v.optional(v.number()),
+      nickname: v.string(),
+      owner: v.id("teams"),
+      bio: v.optional(v.string()),
+      team: v.string(),
+    })"#,
+  );
+
+  let changes: Vec<_> = diff(&old, &new)
+    .into_iter()
+    .map(|change| {
+      (change.path, change.kind.name(), change.kind.compatibility())
+    })
+    .collect();
+  assert_eq!(
+    vec![
+      ("age".into(), "field_made_optional", Compatibility::BreaksOldReaders),
+      ("email".into(), "field_removed", Compatibility::Safe),
+      (
+        "nickname".into(),
+        "field_made_required",
+        Compatibility::BreaksNewReaders
+      ),
+      ("bio".into(), "field_added", Compatibility::Safe),
+      ("team".into(), "field_added", Compatibility::BreaksNewReaders),
+      ("age".into(), "type_changed", Compatibility::Breaking),
+      ("owner".into(), "id_table_changed", Compatibility::Breaking),
+    ],
+    changes,
+  );
+
+  assert!(diff(&old, &old).is_empty());
+}
+
+#[test]
+fn diff_unions() {
+  let old = parse(
+    r#"v.object({
+      platform: v.union(
+        v.object({ platform: v.literal("github"), username: v.string() }),
+        v.object({ platform: v.literal("google"), verified: v.boolean() }),
+      ),
+      status: v.union(v.literal("active"), v.literal("banned")),
+      tags: v.array(v.literal(1)),
+    })"#,
+  );
+  let new = parse(
+    r#"v.object({
+      platform: v.union(
+        v.object({ platform: v.literal("github"), username: v.int64() }),
+        v.object({ platform: v.literal("gitlab"), username: v.string() }),
+      ),
+      status: v.union(v.literal("active"), v.literal("deleted")),
+      tags: v.array(v.literal(2)),
+    })"#,
+  );
+
+  assert_eq!(
+    vec![
+      SchemaChange {
+        path: "platform<github>.username".into(),
+        kind: ChangeKind::TypeChanged {
+          old: "string".into(),
+          new: "int64".into()
+        },
+      },
+      SchemaChange {
+        path: "platform".into(),
+        kind: ChangeKind::UnionBranchRemoved { branch: "google".into() },
+      },
+      SchemaChange {
+        path: "platform".into(),
+        kind: ChangeKind::UnionBranchAdded { branch: "gitlab".into() },
+      },
+      SchemaChange {
+        path: "status".into(),
+        kind: ChangeKind::UnionBranchRemoved { branch: "\"banned\"".into() },
+      },
+      SchemaChange {
+        path: "status".into(),
+        kind: ChangeKind::UnionBranchAdded { branch: "\"deleted\"".into() },
+      },
+      SchemaChange {
+        path: "tags[]".into(),
+        kind: ChangeKind::LiteralChanged {
+          old: Literal::Float64(1.0),
+          new: Literal::Float64(2.0),
+        },
+      },
+    ],
+    diff(&old, &new),
+  );
+}
+
+#[test]
+fn diff_report() {
+  let old = parse("v.object({ a: v.string() })");
+  let new =
+    parse("#[strict] v.object({ a: v.string(), b: v.optional(v.null()) })");
+
+  let changes = diff(&old, &new);
+  assert_eq!(
+    json!([
+      {
+        "path": "",
+        "change": "strict_added",
+        "compatibility": "breaks_new_readers",
+      },
+      {
+        "path": "b",
+        "change": "field_added",
+        "compatibility": "safe",
+        "optional": true,
+      },
+    ]),
+    json!(changes.iter().map(|change| change.to_json()).collect::<Vec<_>>()),
+  );
+  assert_eq!(
+    "(root): made strict (breaks_new_readers)",
+    changes[0].to_string()
+  );
+}
+
+#[test]
+fn diff_records() {
+  let old =
+    parse(r#"v.object({ owners: v.record(v.id("users"), v.int64()) })"#);
+  let new =
+    parse(r#"v.object({ owners: v.record(v.string(), v.array(v.int64())) })"#);
+
+  let changes: Vec<_> = diff(&old, &new)
+    .into_iter()
+    .map(|change| (change.path, change.kind.name()))
+    .collect();
+  assert_eq!(
+    vec![
+      ("owners<keys>".into(), "type_changed"),
+      ("owners<values>".into(), "type_changed"),
+    ],
+    changes,
+  );
+}
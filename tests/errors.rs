@@ -0,0 +1,62 @@
+use convex::{ConvexError, Value};
+use maplit::btreemap;
+use ragkit_convex_macros::convex_error;
+
+convex_error!(AppError {
+  code: v.union(v.literal("NOT_FOUND"), v.literal("FORBIDDEN")),
+  message: v.string(),
+});
+
+convex_error!(LimitError { limit: v.int64() });
+
+#[test]
+fn decode_convex_error() {
+  let error = ConvexError {
+    message: "Uncaught ConvexError".into(),
+    data: Value::Object(btreemap! {
+      "code".into() => Value::String("FORBIDDEN".into()),
+      "message".into() => Value::String("Not your channel".into()),
+    }),
+  };
+
+  let app_error = AppError::try_from(&error);
+  assert!(app_error.is_ok());
+  let app_error = app_error.unwrap();
+  assert!(matches!(app_error.code, AppErrorCode::Variant2(_)));
+  assert_eq!("Not your channel", app_error.to_string());
+
+  let error: Box<dyn std::error::Error> = Box::new(app_error);
+  assert_eq!("Not your channel", error.to_string());
+}
+
+#[test]
+fn decode_convex_error_negative() {
+  let error = ConvexError {
+    message: "Uncaught ConvexError".into(),
+    data: Value::Object(btreemap! {
+      "code".into() => Value::String("TEAPOT".into()),
+      "message".into() => Value::String("I'm a teapot".into()),
+    }),
+  };
+  assert!(AppError::try_from(&error).is_err());
+
+  let error = ConvexError {
+    message: "Uncaught ConvexError".into(),
+    data: Value::String("NOT_FOUND".into()),
+  };
+  assert!(AppError::try_from(&error).is_err());
+}
+
+#[test]
+fn display_without_message() {
+  let error = ConvexError {
+    message: "Uncaught ConvexError".into(),
+    data: Value::Object(btreemap! {
+      "limit".into() => Value::Int64(10),
+    }),
+  };
+
+  let limit_error = LimitError::try_from(&error).unwrap();
+  assert_eq!(10, limit_error.limit);
+  assert_eq!("{\"limit\":10}", limit_error.to_string());
+}
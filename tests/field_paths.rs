@@ -0,0 +1,81 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::FieldPath;
+
+convex_model!(User {
+  _id: v.id("users"),
+  firstName: v.string(),
+  age: v.optional(v.int64()),
+  tags: v.array(v.string()),
+  settings: v.optional(v.object({
+    theme: v.union(v.literal("dark"), v.literal("light")),
+    fontSize: v.number(),
+  })),
+  platform: v.union(
+    v.object({
+      platform: v.literal("github"),
+      username: v.string(),
+      id: v.int64(),
+    }),
+    v.object({
+      platform: v.literal("google"),
+      username: v.string(),
+      id: v.string(),
+    }),
+  ),
+});
+
+#[test]
+fn field_path_constants() {
+  assert_eq!("_id", User::FIELD__ID.path());
+  assert_eq!("firstName", User::FIELD_FIRST_NAME.path());
+  assert_eq!("age", User::FIELD_AGE.path());
+  assert_eq!("settings.theme", User::FIELD_SETTINGS_THEME.path());
+  assert_eq!("settings.fontSize", User::FIELD_SETTINGS_FONT_SIZE.to_string());
+  assert_eq!("platform.platform", User::FIELD_PLATFORM_PLATFORM.path());
+  assert_eq!("platform.username", User::FIELD_PLATFORM_USERNAME.path());
+  assert_eq!("theme", UserSettings::FIELD_THEME.path());
+  assert_eq!("id", UserPlatformVariant1::FIELD_ID.path());
+}
+
+#[test]
+fn field_path_values() {
+  // Optional fields carry their inner type.
+  let age: FieldPath<i64> = User::FIELD_AGE;
+  assert_eq!(("age", Value::Int64(42)), age.eq(&42));
+
+  assert_eq!(
+    ("tags", Value::Array(vec![Value::String("admin".into())])),
+    User::FIELD_TAGS.eq(&vec!["admin".into()]),
+  );
+  assert_eq!(
+    Value::String("light".into()),
+    User::FIELD_SETTINGS_THEME.value(&UserSettingsTheme::light()),
+  );
+  assert_eq!(
+    Value::Object(btreemap! {
+      "platform".into() => Value::String("github".into()),
+      "username".into() => Value::String("alicecodes".into()),
+      "id".into() => Value::Int64(1),
+    }),
+    User::FIELD_PLATFORM.value(&UserPlatform::github("alicecodes".into(), 1)),
+  );
+}
+
+#[test]
+fn nullable_object_paths() {
+  convex_model!(Profile {
+    bio: v.nullable(v.object({
+      headline: v.string(),
+    })),
+  });
+
+  let _: FieldPath<Option<ProfileBio>> = Profile::FIELD_BIO;
+  let headline: FieldPath<String> = Profile::FIELD_BIO_HEADLINE;
+  assert_eq!("bio.headline", headline.path());
+  assert_eq!(
+    ("bio.headline", Value::String("Hi".into())),
+    Profile::FIELD_BIO_HEADLINE.eq(&"Hi".into()),
+  );
+}
@@ -0,0 +1,55 @@
+use ragkit_convex_macros::convex_model;
+
+convex_model!(#[strict] User {
+  _id: v.id("users"),
+  name: v.string(),
+  email: v.string(),
+  age: v.optional(v.int64()),
+  score: v.number(),
+  tags: v.array(v.string()),
+  platform: v.union(
+    v.object({
+      platform: v.literal("github"),
+      username: v.string(),
+    }),
+    v.object({
+      platform: v.literal("google"),
+      verified: v.literal(true),
+    }),
+    v.null(),
+  ),
+});
+
+#[test]
+fn fixtures_are_deterministic() {
+  assert_eq!(User::fixture(1), User::fixture(1));
+  assert_ne!(User::fixture(1), User::fixture(2));
+}
+
+#[test]
+fn fixtures_are_valid() {
+  for seed in 0..100 {
+    let user = User::fixture(seed);
+    let value = user.to_convex_value();
+    assert!(User::convex_schema().validate(&value).is_ok());
+    assert_eq!(user, User::from_convex_value(&value).unwrap());
+
+    assert_eq!(32, user._id.len());
+    assert!(user.email.ends_with("@example.com"));
+    assert!(!user.tags.is_empty());
+    if let UserPlatform::Variant2(google) = &user.platform {
+      assert_eq!("google", google.platform);
+      assert!(google.verified);
+    }
+  }
+}
+
+#[test]
+fn fixtures_cover_branches() {
+  let users: Vec<_> = (0..100).map(User::fixture).collect();
+  assert!(users.iter().any(|user| user.age.is_some()));
+  assert!(users.iter().any(|user| user.age.is_none()));
+  assert!(users.iter().any(|user| user.platform.as_1().is_ok()));
+  assert!(users.iter().any(|user| user.platform.as_2().is_ok()));
+  assert!(users.iter().any(|user| user.platform.as_3().is_ok()));
+}
@@ -0,0 +1,96 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_function;
+
+convex_function!(query "messages:list" {
+  args: {
+    channel: v.id("channels"),
+    limit: v.optional(v.int64()),
+  },
+  returns: v.array(v.object({
+    author: v.string(),
+    body: v.string(),
+  })),
+});
+
+convex_function!(mutation SendMessage "messages:send" {
+  args: {
+    channel: v.id("channels"),
+    body: v.string(),
+  },
+  returns: v.id("messages"),
+});
+
+convex_function!(action "folder/jobs:run" {});
+
+#[test]
+fn function_paths() {
+  assert_eq!("messages:list", MessagesList::PATH);
+  assert_eq!("query", MessagesList::KIND);
+  assert_eq!("messages:send", SendMessage::PATH);
+  assert_eq!("mutation", SendMessage::KIND);
+  assert_eq!("folder/jobs:run", FolderJobsRun::PATH);
+  assert_eq!("action", FolderJobsRun::KIND);
+}
+
+#[test]
+fn function_args() {
+  let args = MessagesListArgs::new("1234".into()).with_limit(Some(10));
+  assert_eq!(
+    Value::Object(btreemap! {
+      "channel".into() => Value::String("1234".into()),
+      "limit".into() => Value::Int64(10),
+    }),
+    args.to_convex_value(),
+  );
+
+  assert_eq!(
+    btreemap! {
+      "channel".into() => Value::String("1234".into()),
+      "limit".into() => Value::Int64(10),
+    },
+    args.to_convex_args(),
+  );
+
+  let args = SendMessageArgs::new("1234".into(), "Hello".into());
+  assert_eq!(
+    Value::Object(btreemap! {
+      "channel".into() => Value::String("1234".into()),
+      "body".into() => Value::String("Hello".into()),
+    }),
+    args.to_convex_value(),
+  );
+
+  assert_eq!(
+    Value::Object(btreemap! {}),
+    FolderJobsRunArgs::new().to_convex_value()
+  );
+}
+
+#[test]
+fn function_returns() {
+  let messages = MessagesList::returns_from_convex_value(&Value::Array(vec![
+    Value::Object(btreemap! {
+      "author".into() => Value::String("Alice".into()),
+      "body".into() => Value::String("Hello".into()),
+    }),
+  ]))
+  .expect("it should parse");
+  assert_eq!(
+    vec![MessagesListReturns { author: "Alice".into(), body: "Hello".into() }],
+    messages,
+  );
+
+  let result =
+    MessagesList::returns_from_convex_value(&Value::String("oops".into()));
+  assert!(result.is_err());
+
+  let id =
+    SendMessage::returns_from_convex_value(&Value::String("5678".into()))
+      .expect("it should parse");
+  assert_eq!("5678", id);
+
+  let value = FolderJobsRun::returns_from_convex_value(&Value::Int64(42))
+    .expect("it should parse");
+  assert_eq!(Value::Int64(42), value);
+}
@@ -0,0 +1,114 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+
+convex_model!(Message {
+  author: v.id("users"),
+  createdAt: v.number(),
+  channel: v.optional(v.id("channels")),
+  meta: v.object({
+    priority: v.int64(),
+  }),
+  body: v.string(),
+}
+.index("by_author", ["author", "createdAt"])
+.index("by_channel_priority", ["channel", "meta.priority"]));
+
+#[test]
+fn index_constants() {
+  assert_eq!("by_author", MessageByAuthor::NAME);
+  assert_eq!(&["author", "createdAt"], MessageByAuthor::FIELDS);
+  assert_eq!("by_channel_priority", MessageByChannelPriority::NAME);
+  assert_eq!(&["channel", "meta.priority"], MessageByChannelPriority::FIELDS);
+}
+
+#[test]
+fn index_args() {
+  let key = MessageByAuthor::new("1234".into(), 1.5);
+  assert_eq!("1234", key.author);
+  assert_eq!(
+    btreemap! {
+      "author".into() => Value::String("1234".into()),
+      "createdAt".into() => Value::Float64(1.5),
+    },
+    key.to_convex_args(),
+  );
+
+  // Optional fields use their inner type.
+  let key = MessageByChannelPriority::new("5678".into(), 2);
+  assert_eq!(
+    btreemap! {
+      "channel".into() => Value::String("5678".into()),
+      "meta.priority".into() => Value::Int64(2),
+    },
+    key.to_convex_args(),
+  );
+}
+
+convex_model!(Document {
+  title: v.string(),
+  body: v.string(),
+  channel: v.id("channels"),
+  meta: v.optional(v.object({
+    draft: v.boolean(),
+  })),
+  embedding: v.array(v.number()),
+}
+.searchIndex("search_body", {
+  searchField: "body",
+  filterFields: ["channel", "meta.draft"],
+})
+.vectorIndex("by_embedding", {
+  vectorField: "embedding",
+  dimensions: 3,
+  filterFields: ["channel"],
+}));
+
+#[test]
+fn search_index() {
+  assert_eq!("search_body", DocumentSearchBody::NAME);
+  assert_eq!("body", DocumentSearchBody::SEARCH_FIELD);
+  assert_eq!(&["channel", "meta.draft"], DocumentSearchBody::FILTER_FIELDS);
+
+  let search = DocumentSearchBody::new("hello".into());
+  assert_eq!(
+    btreemap! { "body".into() => Value::String("hello".into()) },
+    search.to_convex_args(),
+  );
+
+  let search = DocumentSearchBody {
+    meta_draft: Some(false),
+    ..DocumentSearchBody::new("hello".into())
+  };
+  assert_eq!(
+    btreemap! {
+      "body".into() => Value::String("hello".into()),
+      "meta.draft".into() => Value::Boolean(false),
+    },
+    search.to_convex_args(),
+  );
+}
+
+#[test]
+fn vector_index() {
+  assert_eq!("by_embedding", DocumentByEmbedding::NAME);
+  assert_eq!("embedding", DocumentByEmbedding::VECTOR_FIELD);
+  assert_eq!(3, DocumentByEmbedding::DIMENSIONS);
+  assert_eq!(&["channel"], DocumentByEmbedding::FILTER_FIELDS);
+
+  assert!(DocumentByEmbedding::new(vec![1.0, 2.0]).is_err());
+
+  let mut search = DocumentByEmbedding::new(vec![1.0, 2.0, 3.0]).unwrap();
+  search.channel = Some("general".into());
+  assert_eq!(
+    btreemap! {
+      "embedding".into() => Value::Array(vec![
+        Value::Float64(1.0),
+        Value::Float64(2.0),
+        Value::Float64(3.0),
+      ]),
+      "channel".into() => Value::String("general".into()),
+    },
+    search.to_convex_args(),
+  );
+}
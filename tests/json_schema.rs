@@ -0,0 +1,128 @@
+use ragkit_convex_macros::convex_model;
+use serde_json::json;
+
+#[test]
+fn basic_json_schema() {
+  convex_model!(User {
+    _id: v.id("users"),
+    name: v.string(),
+    age: v.optional(v.int64()),
+    score: v.number(),
+    tags: v.array(v.boolean()),
+    nothing: v.null(),
+  });
+
+  assert_eq!(
+    json!({
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "title": "User",
+      "type": "object",
+      "properties": {
+        "_id": { "type": "string", "x-convex-table": "users" },
+        "name": { "type": "string" },
+        "age": { "type": "integer" },
+        "score": {
+          "anyOf": [
+            { "type": "number" },
+            {
+              "type": "object",
+              "properties": { "$float": { "type": "string" } },
+              "required": ["$float"],
+              "additionalProperties": false,
+            },
+          ],
+        },
+        "tags": { "type": "array", "items": { "type": "boolean" } },
+        "nothing": { "type": "null" },
+      },
+      "required": ["_id", "name", "score", "tags", "nothing"],
+    }),
+    User::json_schema(),
+  );
+}
+
+#[test]
+fn union_json_schema() {
+  convex_model!(#[strict] Model {
+    platform: v.union(
+      v.object({
+        platform: v.literal("github"),
+        username: v.string(),
+      }),
+      v.object({
+        platform: v.literal("google"),
+        verified: v.literal(true),
+      }),
+    ),
+    value: v.union(v.string(), v.literal(1)),
+  });
+
+  assert_eq!(
+    json!({
+      "oneOf": [
+        {
+          "type": "object",
+          "properties": {
+            "platform": { "const": "github" },
+            "username": { "type": "string" },
+          },
+          "required": ["platform", "username"],
+          "additionalProperties": false,
+        },
+        {
+          "type": "object",
+          "properties": {
+            "platform": { "const": "google" },
+            "verified": { "const": true },
+          },
+          "required": ["platform", "verified"],
+          "additionalProperties": false,
+        },
+      ],
+      "discriminator": { "propertyName": "platform" },
+    }),
+    ModelPlatform::convex_schema().to_json_schema(),
+  );
+
+  assert_eq!(
+    json!({ "anyOf": [{ "type": "string" }, { "const": 1.0 }] }),
+    ModelValue::convex_schema().to_json_schema(),
+  );
+  assert_eq!(json!(false), Model::json_schema()["additionalProperties"]);
+}
+
+#[test]
+fn union_json_schema_without_discriminant() {
+  convex_model!(Model {
+    a: v.union(
+      v.object({ t: v.literal("one"), value: v.int64() }),
+      v.object({ t: v.literal("one"), value: v.string() }),
+      v.object({ value: v.string() }),
+    ),
+  });
+
+  let schema = ModelA::convex_schema().to_json_schema();
+  assert!(schema.get("anyOf").is_some());
+  assert!(schema.get("discriminator").is_none());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn schemars_json_schema() {
+  convex_model!(User { _id: v.id("users"), name: v.string() });
+
+  let schema = schemars::schema_for!(User);
+  assert_eq!(
+    json!({
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "title": "User",
+      "type": "object",
+      "properties": {
+        "_id": { "type": "string", "x-convex-table": "users" },
+        "name": { "type": "string" },
+      },
+      "required": ["_id", "name"],
+    }),
+    schema.to_value(),
+  );
+}
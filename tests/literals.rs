@@ -0,0 +1,140 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::{Literal, Validator};
+use serde_json::json;
+
+#[test]
+fn number_literals() {
+  convex_model!(Model {
+    negative: v.literal(-1),
+    negative_bigint: v.literal(-1n),
+    bigint: v.literal(9007199254740993n),
+    min: v.literal(-9223372036854775808n),
+    float: v.literal(1.5),
+    negative_float: v.literal(-0.25),
+    large: v.literal(1e20),
+  });
+
+  let convex_data = Value::Object(btreemap! {
+    "negative".into() => Value::Float64(-1.0),
+    "negative_bigint".into() => Value::Int64(-1),
+    "bigint".into() => Value::Int64(9007199254740993),
+    "min".into() => Value::Int64(i64::MIN),
+    "float".into() => Value::Float64(1.5),
+    "negative_float".into() => Value::Float64(-0.25),
+    "large".into() => Value::Float64(1e20),
+  });
+  let model =
+    Model::from_convex_value(&convex_data).expect("Model should parse data");
+  assert_eq!(-1.0, model.negative);
+  assert_eq!(-1, model.negative_bigint);
+  assert_eq!(9007199254740993, model.bigint);
+  assert_eq!(i64::MIN, model.min);
+  assert_eq!(1.5, model.float);
+  assert_eq!(-0.25, model.negative_float);
+  assert_eq!(model, Model::new());
+  assert_eq!(convex_data, model.to_convex_value());
+  assert_eq!(
+    json!({
+      "negative": -1.0,
+      "negative_bigint": -1,
+      "bigint": 9007199254740993i64,
+      "min": i64::MIN,
+      "float": 1.5,
+      "negative_float": -0.25,
+      "large": 1e20,
+    }),
+    json!(model),
+  );
+
+  // Floats only match their exact value.
+  let mut wrong = convex_data.clone();
+  if let Value::Object(object) = &mut wrong {
+    object.insert("float".into(), Value::Float64(1.5000001));
+  }
+  assert!(Model::from_convex_value(&wrong).is_err());
+  assert!(Model::convex_schema().validate(&wrong).is_err());
+  assert!(Model::convex_schema().validate(&convex_data).is_ok());
+}
+
+#[test]
+fn number_literal_unions() {
+  convex_model!(Model {
+    level: v.union(v.literal(-1n), v.literal(0.5), v.literal(2.5)),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "level".into() => Value::Float64(2.5),
+  }))
+  .expect("Model should parse data");
+  assert!(matches!(model.level, ModelLevel::Variant3(value) if value == 2.5));
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "level".into() => Value::Int64(-1),
+  }))
+  .expect("Model should parse data");
+  assert!(matches!(model.level, ModelLevel::Variant1(-1)));
+
+  assert!(Model::from_convex_value(&Value::Object(btreemap! {
+    "level".into() => Value::Float64(1.0),
+  }))
+  .is_err());
+}
+
+#[test]
+fn number_literal_source() {
+  convex_model!(Model {
+    a: v.literal(-1n),
+    b: v.literal(1.0),
+    c: v.literal(-0.25),
+  });
+
+  assert_eq!(
+    "v.object({ a: v.literal(-1n), b: v.literal(1), c: v.literal(-0.25) })",
+    Model::CONVEX_VALIDATOR_TS,
+  );
+  assert_eq!(
+    Validator::parse(Model::CONVEX_VALIDATOR_TS).unwrap().schema_hash(),
+    Model::SCHEMA_HASH,
+  );
+  assert_eq!(
+    Ok(Validator::Literal(Literal::Float64(-0.25))),
+    Validator::parse("v.literal(-0.25)"),
+  );
+  // Like TypeScript, only bigint literals are int64.
+  assert_eq!(
+    Validator::parse("v.literal(1)"),
+    Validator::parse("v.literal(1.0)"),
+  );
+  assert_eq!(
+    Ok(Validator::Literal(Literal::Int64(1))),
+    Validator::parse("v.literal(1n)"),
+  );
+  assert!(Validator::parse("v.literal(-\"a\")").is_err());
+  assert!(Validator::parse("v.literal(9223372036854775808n)").is_err());
+  assert!(Validator::parse("v.literal(1e400)").is_err());
+  assert!(Validator::parse("v.literal(1u8)").is_err());
+}
+
+#[test]
+fn int_literals_need_whole_floats() {
+  convex_model!(Model {
+    level: v.union(v.literal(0n), v.literal(0.5)),
+    negative: v.literal(-1n),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "level".into() => Value::Float64(0.5),
+    "negative".into() => Value::Float64(-1.0),
+  }))
+  .expect("Model should parse data");
+  assert!(matches!(model.level, ModelLevel::Variant2(value) if value == 0.5));
+
+  let truncated = Value::Object(btreemap! {
+    "level".into() => Value::Float64(0.25),
+    "negative".into() => Value::Float64(-1.9),
+  });
+  assert!(Model::from_convex_value(&truncated).is_err());
+  assert!(Model::convex_schema().validate(&truncated).is_err());
+}
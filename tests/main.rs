@@ -41,7 +41,7 @@ fn example() {
   assert_eq!("Hello World", model.six);
   assert_eq!("seven", model.seven);
   assert!(!model.eight);
-  assert_eq!(9, model.nine);
+  assert_eq!(9.0, model.nine);
 
   if let ExampleTen::Variant2(value) = model.ten {
     assert_eq!(10.0, value);
@@ -58,7 +58,7 @@ fn example() {
     "six": "Hello World",
     "seven": "seven",
     "eight": false,
-    "nine": 9,
+    "nine": 9.0,
     "ten": 10.0,
   });
 
@@ -0,0 +1,126 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::Validator;
+use serde_json::json;
+
+#[test]
+fn aliases() {
+  convex_model!(Model { score: v.float64(), count: v.bigint() });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "score".into() => Value::Float64(1.5),
+    "count".into() => Value::Int64(3),
+  }))
+  .expect("Model should parse data");
+  assert_eq!(1.5, model.score);
+  assert_eq!(3, model.count);
+
+  assert_eq!(
+    Validator::parse("v.object({ score: v.number(), count: v.int64() })")
+      .unwrap()
+      .schema_hash(),
+    Model::SCHEMA_HASH,
+  );
+}
+
+#[test]
+fn nullable() {
+  convex_model!(Model {
+    name: v.nullable(v.string()),
+    meta: v.nullable(v.object({ tag: v.string() })),
+    nickname: v.optional(v.nullable(v.string())),
+  });
+
+  // Nullable fields are required.
+  assert!(Model::from_convex_value(&Value::Object(btreemap! {})).is_err());
+
+  let convex_data = Value::Object(btreemap! {
+    "name".into() => Value::Null,
+    "meta".into() => Value::Object(btreemap! {
+      "tag".into() => Value::String("a".into()),
+    }),
+    "nickname".into() => Value::Null,
+  });
+  let model =
+    Model::from_convex_value(&convex_data).expect("Model should parse data");
+  assert_eq!(None, model.name);
+  assert_eq!(Some(ModelMeta::new("a".into())), model.meta);
+  assert_eq!(Some(None), model.nickname);
+
+  // None is sent as an explicit null.
+  assert_eq!(convex_data, model.to_convex_value());
+  assert_eq!(
+    json!({ "name": null, "meta": { "tag": "a" }, "nickname": null }),
+    json!(model),
+  );
+
+  let model = Model::new(Some("Alice".into()), None);
+  assert_eq!(
+    Value::Object(btreemap! {
+      "name".into() => Value::String("Alice".into()),
+      "meta".into() => Value::Null,
+    }),
+    model.to_convex_value(),
+  );
+  assert_eq!(json!({ "name": "Alice", "meta": null }), json!(model));
+  assert_eq!(
+    model,
+    serde_json::from_value(json!({ "name": "Alice", "meta": null })).unwrap(),
+  );
+
+  assert!(Model::from_convex_value(&Value::Object(btreemap! {
+    "name".into() => Value::Int64(1),
+    "meta".into() => Value::Null,
+  }))
+  .is_err());
+}
+
+#[test]
+fn optional_nullable_round_trip() {
+  convex_model!(Model { nickname: v.optional(v.nullable(v.string())) });
+
+  for model in [
+    Model { nickname: None },
+    Model { nickname: Some(None) },
+    Model { nickname: Some(Some("Al".into())) },
+  ] {
+    let round_trip: Model = serde_json::from_value(json!(model)).unwrap();
+    assert_eq!(model, round_trip);
+  }
+  assert_eq!(
+    Model { nickname: Some(None) },
+    serde_json::from_value(json!({ "nickname": null })).unwrap(),
+  );
+  assert_eq!(
+    Model { nickname: None },
+    serde_json::from_value(json!({})).unwrap()
+  );
+}
+
+#[test]
+fn nullable_schema() {
+  convex_model!(Model { name: v.nullable(v.string()) });
+
+  assert_eq!(
+    "v.object({ name: v.nullable(v.string()) })",
+    Model::CONVEX_VALIDATOR_TS
+  );
+  let schema = Model::convex_schema();
+  assert!(schema
+    .validate(&Value::Object(btreemap! { "name".into() => Value::Null }))
+    .is_ok());
+  assert!(schema.validate(&Value::Object(btreemap! {})).is_err());
+
+  // Convex expands v.nullable(x) into v.union(x, v.null()).
+  let deployed =
+    Validator::parse("v.object({ name: v.union(v.string(), v.null()) })")
+      .unwrap();
+  assert_eq!(deployed.schema_hash(), Model::SCHEMA_HASH);
+  assert!(ragkit_convex_runtime::diff(&deployed, schema).is_empty());
+
+  assert_eq!(
+    json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] }),
+    Model::json_schema()["properties"]["name"],
+  );
+}
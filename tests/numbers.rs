@@ -0,0 +1,123 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use serde_json::json;
+
+#[test]
+fn basic_number() {
+  convex_model!(Model { a: v.number() });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::Float64(3.5),
+  });
+  let json_data = json!({
+    "a": 3.5,
+  });
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(3.5, model.a);
+  assert_eq!(json_data, json!(model));
+  assert_eq!(convex_data, model.to_convex_value());
+}
+
+#[test]
+fn special_floats_json() {
+  convex_model!(Model { a: v.number() });
+
+  for f in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.0] {
+    let model = Model { a: f };
+    let json_data = json!({
+      "a": serde_json::Value::from(Value::Float64(f)),
+    });
+    assert!(json_data["a"]["$float"].is_string());
+    assert_eq!(json_data, json!(model));
+    assert_eq!(json_data, serde_json::Value::from(model));
+  }
+}
+
+#[test]
+fn special_floats_roundtrip() {
+  convex_model!(Model {
+    a: v.number(),
+    b: v.optional(v.number()),
+    c: v.union(v.string(), v.number()),
+  });
+
+  for f in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.0, 0.0] {
+    let convex_data = Value::Object(btreemap! {
+      "a".into() => Value::Float64(f),
+      "b".into() => Value::Float64(f),
+      "c".into() => Value::Float64(f),
+    });
+
+    let model = Model::from_convex_value(&convex_data);
+    assert!(model.is_ok());
+    let model = model.unwrap();
+    assert_eq!(f.to_bits(), model.a.to_bits());
+    assert_eq!(f.to_bits(), model.b.unwrap().to_bits());
+    assert_eq!(f.to_bits(), model.c.as_2().unwrap().to_bits());
+
+    let Value::Object(object) = model.to_convex_value() else {
+      panic!("Expected an object");
+    };
+    for key in ["a", "b", "c"] {
+      let Some(Value::Float64(value)) = object.get(key) else {
+        panic!("Expected '{}' to be a float", key);
+      };
+      assert_eq!(f.to_bits(), value.to_bits());
+    }
+
+    let json_data = json!(model);
+    for key in ["a", "b", "c"] {
+      assert_eq!(serde_json::Value::from(Value::Float64(f)), json_data[key]);
+    }
+  }
+}
+
+#[test]
+fn special_floats_json_roundtrip() {
+  convex_model!(Model {
+    a: v.number(),
+    b: v.optional(v.number()),
+    c: v.array(v.float64()),
+    d: v.nullable(v.number()),
+    e: v.optional(v.nullable(v.number())),
+    f: v.record(v.string(), v.number()),
+  });
+
+  for f in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.0, 1.5] {
+    let model = Model {
+      a: f,
+      b: Some(f),
+      c: vec![f, 2.0],
+      d: Some(f),
+      e: Some(Some(f)),
+      f: btreemap! { "x".into() => f },
+    };
+    let round_trip: Model = serde_json::from_value(json!(model)).unwrap();
+    assert_eq!(f.to_bits(), round_trip.a.to_bits());
+    assert_eq!(f.to_bits(), round_trip.b.unwrap().to_bits());
+    assert_eq!(f.to_bits(), round_trip.c[0].to_bits());
+    assert_eq!(f.to_bits(), round_trip.d.unwrap().to_bits());
+    assert_eq!(f.to_bits(), round_trip.e.unwrap().unwrap().to_bits());
+    assert_eq!(f.to_bits(), round_trip.f["x"].to_bits());
+  }
+
+  let model: Model = serde_json::from_value(json!({
+    "a": 1,
+    "c": [],
+    "d": null,
+    "e": null,
+    "f": {},
+  }))
+  .unwrap();
+  assert_eq!(
+    (1.0, None, None, Some(None)),
+    (model.a, model.b, model.d, model.e)
+  );
+  assert!(serde_json::from_value::<Model>(
+    json!({ "a": "1", "c": [], "d": null, "f": {} })
+  )
+  .is_err());
+}
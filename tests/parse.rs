@@ -0,0 +1,112 @@
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::{ParseError, Validator};
+
+#[test]
+fn parse_same_as_model() {
+  convex_model!(#[strict] User {
+    _id: v.id("users"),
+    name: v.string(),
+    age: v.optional(v.int64()),
+    tags: v.array(v.string()),
+    platform: v.union(
+      v.object({
+        platform: v.literal("github"),
+        username: v.string(),
+      }),
+      v.object({
+        platform: v.literal("google"),
+        verified: v.literal(true),
+        version: v.literal(2n),
+      }),
+    ),
+  });
+
+  let validator = Validator::parse(
+    r#"#[strict] v.object({
+      _id: v.id("users"),
+      name: v.string(),
+      age: v.optional(v.int64()),
+      tags: v.array(v.string()),
+      platform: v.union(
+        #[strict] v.object({
+          platform: v.literal("github"),
+          username: v.string(),
+        }),
+        #[strict] v.object({
+          platform: v.literal("google"),
+          verified: v.literal(true),
+          version: v.literal(2n),
+        }),
+      ),
+    })"#,
+  );
+  assert_eq!(Ok(User::convex_schema()), validator.as_ref());
+}
+
+#[test]
+fn parse_generated_ts() {
+  convex_model!(Model {
+    a: v.union(v.string(), v.null()),
+    b: v.object({ c: v.number(), ..rest }),
+  });
+
+  let validator = Validator::parse(ModelA::CONVEX_VALIDATOR_TS);
+  assert_eq!(Ok(ModelA::convex_schema()), validator.as_ref());
+}
+
+#[test]
+fn parse_errors() {
+  assert_eq!(
+    Err(ParseError {
+      message: "Unsupported validator call".into(),
+      line: 3,
+      column: 8,
+    }),
+    Validator::parse("v.object({\n  a: v.string(),\n  b: v.bytes(),\n})"),
+  );
+
+  let error = Validator::parse("v.array(v.optional(v.string()))").unwrap_err();
+  assert_eq!(
+    "Arrays may not contain optional values at 1:3",
+    error.to_string()
+  );
+
+  let error =
+    Validator::parse("v.union(v.nullable(v.string()), v.int64())").unwrap_err();
+  assert_eq!(
+    "Unions may not contain nullable values, add v.null() to the union \
+     instead at 1:3",
+    error.to_string()
+  );
+
+  for (source, message) in [
+    (
+      "v.union(v.optional(v.string()), v.null())",
+      "Unions may not contain optional branches",
+    ),
+    (
+      "v.union(v.string(), v.union(v.int64(), v.null()))",
+      "Unions may not directly contain other unions, put other types between \
+       them",
+    ),
+    (
+      "v.union(v.array(v.object({ a: v.string() })), v.null())",
+      "Unions may not contain arrays or records of objects or unions",
+    ),
+    (
+      "v.union(v.record(v.string(), v.union(v.string(), v.null())), v.null())",
+      "Unions may not contain arrays or records of objects or unions",
+    ),
+  ] {
+    let error = Validator::parse(source).unwrap_err();
+    assert_eq!(format!("{} at 1:3", message), error.to_string());
+  }
+  assert!(Validator::parse(
+    "v.union(v.array(v.string()), v.record(v.string(), v.int64()))"
+  )
+  .is_ok());
+
+  assert!(Validator::parse("v.union(v.string())").is_err());
+  assert!(Validator::parse("v.object({ ..rest, a: v.string() })").is_err());
+  assert!(Validator::parse("v.string() v.string()").is_err());
+}
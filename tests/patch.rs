@@ -0,0 +1,134 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+
+convex_model!(User {
+  _id: v.id("users"),
+  name: v.string(),
+  age: v.optional(v.int64()),
+  nickname: v.optional(v.nullable(v.string())),
+  settings: v.object({
+    theme: v.string(),
+  }),
+});
+
+fn user() -> User {
+  User {
+    _id: "1234".into(),
+    name: "Alice".into(),
+    age: Some(42),
+    nickname: Some(Some("Al".into())),
+    settings: UserSettings { theme: "dark".into() },
+  }
+}
+
+fn patch_value(set: Value, unset: &[&str]) -> Value {
+  Value::Object(btreemap! {
+    "set".into() => set,
+    "unset".into() => Value::Array(
+      unset.iter().map(|field| Value::String(field.to_string())).collect()
+    ),
+  })
+}
+
+#[test]
+fn empty_patch() {
+  let patch = UserPatch::default();
+  assert_eq!(
+    patch_value(Value::Object(btreemap! {}), &[]),
+    patch.to_convex_value()
+  );
+
+  let mut model = user();
+  patch.apply(&mut model);
+  assert_eq!(user(), model);
+}
+
+#[test]
+fn set_fields() {
+  let patch = UserPatch {
+    name: Some("Bob".into()),
+    age: Some(Some(7)),
+    nickname: None,
+    settings: Some(UserSettings { theme: "light".into() }),
+  };
+  assert_eq!(
+    patch_value(
+      Value::Object(btreemap! {
+        "name".into() => Value::String("Bob".into()),
+        "age".into() => Value::Int64(7),
+        "settings".into() => Value::Object(btreemap! {
+          "theme".into() => Value::String("light".into()),
+        }),
+      }),
+      &[],
+    ),
+    patch.to_convex_value(),
+  );
+
+  let mut model = user();
+  patch.apply(&mut model);
+  assert_eq!("1234", model._id);
+  assert_eq!("Bob", model.name);
+  assert_eq!(Some(7), model.age);
+  assert_eq!("light", model.settings.theme);
+}
+
+#[test]
+fn unset_optional_field() {
+  let patch = UserPatch { age: Some(None), ..Default::default() };
+  assert_eq!(
+    patch_value(Value::Object(btreemap! {}), &["age"]),
+    patch.to_convex_value(),
+  );
+
+  let mut model = user();
+  patch.apply(&mut model);
+  assert_eq!("Alice", model.name);
+  assert_eq!(None, model.age);
+}
+
+#[test]
+fn unset_differs_from_null() {
+  let unset = UserPatch { nickname: Some(None), ..Default::default() };
+  assert_eq!(
+    patch_value(Value::Object(btreemap! {}), &["nickname"]),
+    unset.to_convex_value(),
+  );
+  let mut model = user();
+  unset.apply(&mut model);
+  assert_eq!(None, model.nickname);
+
+  let null = UserPatch { nickname: Some(Some(None)), ..Default::default() };
+  assert_eq!(
+    patch_value(
+      Value::Object(btreemap! { "nickname".into() => Value::Null }),
+      &[],
+    ),
+    null.to_convex_value(),
+  );
+  let mut model = user();
+  null.apply(&mut model);
+  assert_eq!(Some(None), model.nickname);
+
+  assert_ne!(unset.to_convex_value(), null.to_convex_value());
+}
+
+#[test]
+fn patch_field() {
+  convex_model!(Release {
+    version: v.string(),
+    patch: v.object({ number: v.int64() }),
+  });
+
+  let release =
+    Release { version: "1.0".into(), patch: ReleasePatch { number: 1 } };
+  let patch = ReleasePatch2 {
+    patch: Some(ReleasePatch { number: 2 }),
+    ..Default::default()
+  };
+  let mut model = release.clone();
+  patch.apply(&mut model);
+  assert_eq!(2, model.patch.number);
+  assert_eq!("1.0", model.version);
+}
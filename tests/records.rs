@@ -0,0 +1,109 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::Validator;
+use serde_json::json;
+
+convex_model!(Model {
+  scores: v.record(v.string(), v.number()),
+  owners: v.record(v.id("users"), v.object({ role: v.string() })),
+  value: v.union(v.string(), v.record(v.string(), v.int64())),
+});
+
+#[test]
+fn records() {
+  let convex_data = Value::Object(btreemap! {
+    "scores".into() => Value::Object(btreemap! {
+      "alice".into() => Value::Float64(1.5),
+      "bob".into() => Value::Int64(2),
+    }),
+    "owners".into() => Value::Object(btreemap! {
+      "u1".into() => Value::Object(btreemap! {
+        "role".into() => Value::String("admin".into()),
+      }),
+    }),
+    "value".into() => Value::Object(btreemap! {
+      "a".into() => Value::Int64(1),
+    }),
+  });
+  let model =
+    Model::from_convex_value(&convex_data).expect("Model should parse data");
+  assert_eq!(
+    btreemap! { "alice".into() => 1.5, "bob".into() => 2.0 },
+    model.scores
+  );
+  assert_eq!("admin", model.owners["u1"].role);
+  assert_eq!(ModelValue::Variant2(btreemap! { "a".into() => 1 }), model.value);
+
+  assert_eq!(
+    json!({
+      "scores": { "alice": 1.5, "bob": 2.0 },
+      "owners": { "u1": { "role": "admin" } },
+      "value": { "a": 1 },
+    }),
+    json!(model),
+  );
+  assert!(Model::convex_schema().validate(&model.to_convex_value()).is_ok());
+  assert_eq!(
+    model,
+    Model::from_convex_value(&model.to_convex_value()).unwrap()
+  );
+
+  assert!(Model::from_convex_value(&Value::Object(btreemap! {
+    "scores".into() => Value::Object(btreemap! {
+      "alice".into() => Value::String("high".into()),
+    }),
+    "owners".into() => Value::Object(btreemap! {}),
+    "value".into() => Value::String("a".into()),
+  }))
+  .is_err());
+}
+
+#[test]
+fn record_schema() {
+  let source = "v.object({ scores: v.record(v.string(), v.number()), owners: \
+                v.record(v.id(\"users\"), v.object({ role: v.string() })), \
+                value: v.union(v.string(), v.record(v.string(), v.int64())) })";
+  assert_eq!(source, Model::CONVEX_VALIDATOR_TS);
+  let parsed = Validator::parse(source).unwrap();
+  assert_eq!(&parsed, Model::convex_schema());
+  assert_eq!(parsed.schema_hash(), Model::SCHEMA_HASH);
+
+  assert_eq!(
+    json!({
+      "type": "object",
+      "propertyNames": { "type": "string" },
+      "additionalProperties": Validator::Number.to_json_schema(),
+    }),
+    Model::json_schema()["properties"]["scores"],
+  );
+
+  let errors = Model::convex_schema()
+    .validate(&Value::Object(btreemap! {
+      "scores".into() => Value::Object(btreemap! {
+        "alice".into() => Value::Boolean(true),
+      }),
+      "owners".into() => Value::Null,
+      "value".into() => Value::Null,
+    }))
+    .unwrap_err();
+  let paths: Vec<&str> =
+    errors.0.iter().map(|error| error.path.as_str()).collect();
+  assert_eq!(vec!["scores.alice", "owners", "value"], paths);
+
+  assert!(Validator::parse("v.record(v.int64(), v.string())").is_err());
+  assert!(Validator::parse("v.record(v.string())").is_err());
+  assert!(
+    Validator::parse("v.record(v.string(), v.optional(v.string()))").is_err()
+  );
+}
+
+#[test]
+fn record_fixtures() {
+  for seed in 0..20 {
+    let model = Model::fixture(seed);
+    let value = model.to_convex_value();
+    assert!(Model::convex_schema().validate(&value).is_ok());
+    assert_eq!(model, Model::from_convex_value(&value).unwrap());
+  }
+}
@@ -0,0 +1,136 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use serde_json::json;
+
+#[test]
+fn basic_rest() {
+  convex_model!(Model { a: v.string(), ..rest });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+    "b".into() => Value::String("banana".into()),
+    "c".into() => Value::Boolean(true),
+  });
+  let json_data = json!({
+    "a": "apple",
+    "b": "banana",
+    "c": true,
+  });
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!("apple", model.a);
+  assert_eq!(
+    btreemap! {
+      "b".into() => Value::String("banana".into()),
+      "c".into() => Value::Boolean(true),
+    },
+    model.rest,
+  );
+  assert_eq!(json_data, json!(model));
+  assert_eq!(json_data, serde_json::Value::from(model.clone()));
+  assert_eq!(convex_data, model.to_convex_value());
+}
+
+#[test]
+fn rest_is_lossless() {
+  convex_model!(Model { a: v.int64(), ..extra });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::Int64(1),
+    "b".into() => Value::Int64(2),
+    "c".into() => Value::Bytes(vec![1, 2, 3]),
+  });
+
+  let mut model = Model::from_convex_value(&convex_data).unwrap();
+  model.a += 1;
+  let json_data = json!(model);
+  assert_eq!(json!(2), json_data["a"]);
+  assert_eq!(serde_json::Value::from(Value::Int64(2)), json_data["b"]);
+  assert_eq!(
+    serde_json::Value::from(Value::Bytes(vec![1, 2, 3])),
+    json_data["c"]
+  );
+
+  let updated = Value::Object(btreemap! {
+    "a".into() => Value::Int64(2),
+    "b".into() => Value::Int64(2),
+    "c".into() => Value::Bytes(vec![1, 2, 3]),
+  });
+  assert_eq!(updated, model.to_convex_value());
+  let parsed = Value::try_from(json_data).unwrap();
+  assert_eq!(model, Model::from_convex_value(&parsed).unwrap());
+}
+
+#[test]
+fn rest_json_round_trip() {
+  convex_model!(Model { name: v.string(), ..rest });
+  let model = Model {
+    name: "Alice".into(),
+    rest: btreemap! {
+      "age".into() => Value::Int64(42),
+      "bytes".into() => Value::Bytes(vec![1, 2, 3]),
+      "score".into() => Value::Float64(f64::INFINITY),
+      "tags".into() => Value::Array(vec![Value::String("a".into())]),
+    },
+  };
+
+  let round_trip: Model = serde_json::from_value(json!(model)).unwrap();
+  assert_eq!(model, round_trip);
+
+  let model: Model = serde_json::from_value(json!({ "name": "Bob" })).unwrap();
+  assert!(model.rest.is_empty());
+  assert!(serde_json::from_value::<Model>(json!({
+    "name": "Bob",
+    "bad": { "$integer": "not base64" },
+  }))
+  .is_err());
+}
+
+#[test]
+fn declared_fields_win_over_rest() {
+  convex_model!(Model { a: v.string(), ..rest });
+  let model = Model {
+    a: "apple".into(),
+    rest: btreemap! {
+      "a".into() => Value::String("avocado".into()),
+    },
+  };
+  assert_eq!(json!({ "a": "apple" }), json!(model));
+  assert_eq!(
+    Value::Object(btreemap! {
+      "a".into() => Value::String("apple".into()),
+    }),
+    model.to_convex_value(),
+  );
+}
+
+#[test]
+fn nested_rest() {
+  convex_model!(#[strict] Model {
+    a: v.object({
+      b: v.string(),
+      ..rest
+    }),
+  });
+  let convex_data = Value::Object(btreemap! {
+    "a".into() => Value::Object(btreemap! {
+      "b".into() => Value::String("banana".into()),
+      "c".into() => Value::String("cherry".into()),
+    }),
+  });
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(1, model.a.rest.len());
+  assert_eq!(convex_data, model.to_convex_value());
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Object(btreemap! {
+      "b".into() => Value::String("banana".into()),
+    }),
+    "d".into() => Value::String("durian".into()),
+  }));
+  assert!(model.is_err());
+}
@@ -0,0 +1,108 @@
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::{
+  FieldValidator,
+  Literal,
+  ObjectValidator,
+  Validator,
+};
+
+#[test]
+fn basic_schema() {
+  convex_model!(User {
+    _id: v.id("users"),
+    name: v.string(),
+    age: v.optional(v.int64()),
+    tags: v.array(v.string()),
+  });
+
+  let schema = User::convex_schema();
+  assert_eq!(
+    &Validator::Object(ObjectValidator {
+      fields: vec![
+        FieldValidator {
+          name: "_id".into(),
+          validator: Validator::Id("users".into())
+        },
+        FieldValidator { name: "name".into(), validator: Validator::String },
+        FieldValidator {
+          name: "age".into(),
+          validator: Validator::Optional(Box::new(Validator::Int64)),
+        },
+        FieldValidator {
+          name: "tags".into(),
+          validator: Validator::Array(Box::new(Validator::String)),
+        },
+      ],
+      strict: false,
+      rest: None,
+    }),
+    schema,
+  );
+
+  let fields = schema.fields().unwrap();
+  let optional: Vec<_> = fields
+    .iter()
+    .filter(|field| field.is_optional())
+    .map(|field| field.name.as_str())
+    .collect();
+  assert_eq!(vec!["age"], optional);
+
+  // The schema is built once.
+  assert!(std::ptr::eq(schema, User::convex_schema()));
+}
+
+#[test]
+fn nested_schema() {
+  convex_model!(#[strict] Model {
+    platform: v.union(
+      v.object({
+        platform: v.literal("github"),
+        username: v.string(),
+      }),
+      v.literal(true),
+      v.literal(2),
+      v.null(),
+    ),
+    extra: v.object({
+      score: v.number(),
+      ..rest
+    }),
+  });
+
+  let platform = Validator::Union(vec![
+    Validator::Object(ObjectValidator {
+      fields: vec![
+        FieldValidator {
+          name: "platform".into(),
+          validator: Validator::Literal(Literal::String("github".into())),
+        },
+        FieldValidator {
+          name: "username".into(),
+          validator: Validator::String,
+        },
+      ],
+      strict: true,
+      rest: None,
+    }),
+    Validator::Literal(Literal::Boolean(true)),
+    Validator::Literal(Literal::Float64(2.0)),
+    Validator::Null,
+  ]);
+  assert_eq!(&platform, ModelPlatform::convex_schema());
+
+  let Validator::Object(extra) = ModelExtra::convex_schema() else {
+    panic!("Expected an object");
+  };
+  assert!(!extra.strict);
+  assert_eq!(Some("rest".into()), extra.rest);
+  assert_eq!(
+    Some(&Validator::Number),
+    extra.field("score").map(|field| &field.validator)
+  );
+
+  let Validator::Object(model) = Model::convex_schema() else {
+    panic!("Expected an object");
+  };
+  assert!(model.strict);
+  assert_eq!(Some(&platform), model.field("platform").map(|f| &f.validator));
+}
@@ -0,0 +1,60 @@
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::Validator;
+
+#[test]
+fn schema_hash_matches_runtime() {
+  convex_model!(User {
+    _id: v.id("users"),
+    name: v.string(),
+    age: v.optional(v.int64()),
+    platform: v.union(v.literal("github"), v.literal(2n), v.literal(true)),
+  });
+
+  assert_eq!(User::convex_schema().schema_hash(), User::SCHEMA_HASH);
+  assert_eq!(
+    UserPlatform::convex_schema().schema_hash(),
+    UserPlatform::SCHEMA_HASH
+  );
+
+  // Field order, strict and rest fields don't change the hash.
+  let deployed = Validator::parse(
+    r#"#[strict] v.object({
+      platform: v.union(v.literal("github"), v.literal(2n), v.literal(true)),
+      age: v.optional(v.int64()),
+      name: v.string(),
+      _id: v.id("users"),
+    })"#,
+  )
+  .unwrap();
+  assert_eq!(deployed.schema_hash(), User::SCHEMA_HASH);
+}
+
+#[test]
+fn schema_hash_changes() {
+  convex_model!(A { name: v.string() });
+  convex_model!(B { name: v.optional(v.string()) });
+  convex_model!(C { name: v.id("users") });
+  convex_model!(D { name: v.id("teams") });
+  convex_model!(E { title: v.string() });
+
+  let hashes = [
+    A::SCHEMA_HASH,
+    B::SCHEMA_HASH,
+    C::SCHEMA_HASH,
+    D::SCHEMA_HASH,
+    E::SCHEMA_HASH,
+  ];
+  for (i, a) in hashes.iter().enumerate() {
+    for b in &hashes[i + 1..] {
+      assert_ne!(a, b);
+    }
+  }
+}
+
+#[test]
+fn schema_hash_is_stable() {
+  convex_model!(Model { name: v.string() });
+
+  // Changing the algorithm would break comparisons with stored fingerprints.
+  assert_eq!(0x91835DC6DCCF3B41, Model::SCHEMA_HASH);
+}
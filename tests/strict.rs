@@ -0,0 +1,116 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+
+#[test]
+fn permissive_by_default() {
+  convex_model!(Model { a: v.string() });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+    "b".into() => Value::String("banana".into()),
+  }));
+  assert!(model.is_ok());
+}
+
+#[test]
+fn strict_model() {
+  convex_model!(#[strict] Model {
+    a: v.string(),
+    b: v.object({
+      c: v.int64(),
+    }),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+    "b".into() => Value::Object(btreemap! {
+      "c".into() => Value::Int64(42),
+    }),
+  }));
+  assert!(model.is_ok());
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+    "b".into() => Value::Object(btreemap! {
+      "c".into() => Value::Int64(42),
+    }),
+    "x".into() => Value::Null,
+    "y".into() => Value::Null,
+  }));
+  assert_eq!(
+    "Unexpected fields in 'Model': x, y",
+    model.unwrap_err().to_string(),
+  );
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::String("apple".into()),
+    "b".into() => Value::Object(btreemap! {
+      "c".into() => Value::Int64(42),
+      "d".into() => Value::Int64(42),
+    }),
+  }));
+  assert_eq!(
+    "Unexpected fields in 'ModelB': d",
+    model.unwrap_err().to_string(),
+  );
+}
+
+#[test]
+fn strict_object() {
+  convex_model!(Model {
+    a: #[strict] v.object({
+      b: v.string(),
+    }),
+    c: v.object({
+      d: v.string(),
+    }),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Object(btreemap! {
+      "b".into() => Value::String("banana".into()),
+    }),
+    "c".into() => Value::Object(btreemap! {
+      "d".into() => Value::String("durian".into()),
+      "e".into() => Value::String("elderberry".into()),
+    }),
+    "f".into() => Value::String("fig".into()),
+  }));
+  assert!(model.is_ok());
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Object(btreemap! {
+      "b".into() => Value::String("banana".into()),
+      "e".into() => Value::String("elderberry".into()),
+    }),
+    "c".into() => Value::Object(btreemap! {
+      "d".into() => Value::String("durian".into()),
+    }),
+  }));
+  assert!(model.is_err());
+}
+
+#[test]
+fn strict_union_branches() {
+  convex_model!(Model {
+    a: v.union(
+      #[strict] v.object({
+        b: v.string(),
+      }),
+      v.object({
+        b: v.string(),
+        c: v.string(),
+      }),
+    ),
+  });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Object(btreemap! {
+      "b".into() => Value::String("banana".into()),
+      "c".into() => Value::String("cherry".into()),
+    }),
+  }))
+  .expect("it should parse");
+  assert!(model.a.as_2().is_ok());
+}
@@ -121,6 +121,31 @@ fn basic_optional_string() {
   assert_eq!(Some("apples".into()), model.a);
   assert_eq!(json_data, json!(model));
 
+  let convex_data = Value::Object(btreemap! {});
+  let json_data = json!({});
+
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(None, model.a);
+  assert_eq!(json_data, json!(model));
+  assert_eq!(convex_data, model.to_convex_value());
+}
+
+#[test]
+fn basic_optional_string_negative() {
+  convex_model!(Model { a: v.optional(v.string()) });
+
+  let model = Model::from_convex_value(&Value::Object(btreemap! {
+    "a".into() => Value::Null,
+  }));
+  assert!(model.is_err());
+}
+
+#[test]
+fn optional_nullable_string() {
+  convex_model!(Model { a: v.optional(v.union(v.string(), v.null())) });
+
   let convex_data = Value::Object(btreemap! {
     "a".into() => Value::Null,
   });
@@ -128,11 +153,22 @@ fn basic_optional_string() {
     "a": null,
   });
 
+  let model = Model::from_convex_value(&convex_data);
+  assert!(model.is_ok());
+  let model = model.unwrap();
+  assert_eq!(Some(ModelA::Variant2), model.a);
+  assert_eq!(json_data, json!(model));
+  assert_eq!(convex_data, model.to_convex_value());
+
+  let convex_data = Value::Object(btreemap! {});
+  let json_data = json!({});
+
   let model = Model::from_convex_value(&convex_data);
   assert!(model.is_ok());
   let model = model.unwrap();
   assert_eq!(None, model.a);
   assert_eq!(json_data, json!(model));
+  assert_eq!(convex_data, model.to_convex_value());
 }
 
 #[test]
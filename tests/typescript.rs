@@ -0,0 +1,80 @@
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::Validator;
+
+#[test]
+fn basic_validator_ts() {
+  convex_model!(User {
+    _id: v.id("users"),
+    name: v.string(),
+    age: v.optional(v.int64()),
+    score: v.number(),
+    verified: v.boolean(),
+    nothing: v.null(),
+    tags: v.array(v.string()),
+  });
+
+  assert_eq!(
+    "v.object({ _id: v.id(\"users\"), name: v.string(), age: \
+     v.optional(v.int64()), score: v.number(), verified: v.boolean(), \
+     nothing: v.null(), tags: v.array(v.string()) })",
+    User::CONVEX_VALIDATOR_TS,
+  );
+}
+
+#[test]
+fn nested_validator_ts() {
+  convex_model!(Model {
+    platform: v.union(
+      v.object({
+        platform: v.literal("github"),
+        username: v.string(),
+      }),
+      v.object({
+        platform: v.literal("google"),
+        verified: v.literal(true),
+        version: v.literal(2),
+      }),
+    ),
+    settings: v.object({}),
+  });
+
+  let platform = "v.union(v.object({ platform: v.literal(\"github\"), \
+                  username: v.string() }), v.object({ platform: \
+                  v.literal(\"google\"), verified: v.literal(true), version: \
+                  v.literal(2) }))";
+  assert_eq!(platform, ModelPlatform::CONVEX_VALIDATOR_TS);
+  assert_eq!("v.object({})", ModelSettings::CONVEX_VALIDATOR_TS);
+  assert_eq!(
+    format!("v.object({{ platform: {}, settings: v.object({{}}) }})", platform),
+    Model::CONVEX_VALIDATOR_TS,
+  );
+}
+
+#[test]
+fn escaped_validator_ts() {
+  convex_model!(Model { quote: v.literal("say \"hi\"\n") });
+
+  assert_eq!(
+    "v.object({ quote: v.literal(\"say \\\"hi\\\"\\n\") })",
+    Model::CONVEX_VALIDATOR_TS,
+  );
+}
+
+#[test]
+fn number_and_bigint_literals_ts() {
+  convex_model!(Model {
+    number: v.literal(2),
+    float: v.literal(2.5),
+    bigint: v.literal(2n),
+  });
+
+  assert_eq!(
+    "v.object({ number: v.literal(2), float: v.literal(2.5), bigint: \
+     v.literal(2n) })",
+    Model::CONVEX_VALIDATOR_TS,
+  );
+  assert_eq!(
+    Validator::parse(Model::CONVEX_VALIDATOR_TS).as_ref(),
+    Ok(Model::convex_schema()),
+  );
+}
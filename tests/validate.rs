@@ -0,0 +1,139 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+use ragkit_convex_runtime::{
+  FieldValidator,
+  ObjectValidator,
+  ValidationError,
+  Validator,
+};
+
+convex_model!(#[strict] User {
+  _id: v.id("users"),
+  name: v.string(),
+  age: v.optional(v.int64()),
+  tags: v.array(v.string()),
+  platform: v.union(
+    v.object({
+      platform: v.literal("github"),
+      username: v.string(),
+    }),
+    v.object({
+      platform: v.literal("google"),
+      verified: v.boolean(),
+    }),
+    v.null(),
+  ),
+});
+
+fn user(fields: Vec<(&str, Value)>) -> Value {
+  let mut object = btreemap! {
+    "_id".into() => Value::String("1234".into()),
+    "name".into() => Value::String("Alice".into()),
+    "tags".into() => Value::Array(vec![Value::String("admin".into())]),
+    "platform".into() => Value::Null,
+  };
+  for (key, value) in fields {
+    match value {
+      // Use an empty object to remove a field.
+      | Value::Object(o) if o.is_empty() => object.remove(key),
+      | value => object.insert(key.into(), value),
+    };
+  }
+  Value::Object(object)
+}
+
+#[test]
+fn validate_same_as_model() {
+  let values = vec![
+    user(vec![]),
+    user(vec![("age", Value::Int64(42))]),
+    user(vec![("age", Value::Float64(42.0))]),
+    user(vec![("age", Value::Null)]),
+    user(vec![("age", Value::String("42".into()))]),
+    user(vec![("name", Value::Object(btreemap! {}))]),
+    user(vec![("extra", Value::Boolean(true))]),
+    user(vec![("tags", Value::Array(vec![Value::Int64(1)]))]),
+    user(vec![(
+      "platform",
+      Value::Object(btreemap! {
+        "platform".into() => Value::String("github".into()),
+        "username".into() => Value::String("alicecodes".into()),
+      }),
+    )]),
+    user(vec![(
+      "platform",
+      Value::Object(btreemap! {
+        "platform".into() => Value::String("github".into()),
+        "verified".into() => Value::Boolean(true),
+      }),
+    )]),
+    Value::Array(vec![]),
+  ];
+
+  for value in &values {
+    assert_eq!(
+      User::from_convex_value(value).is_ok(),
+      User::convex_schema().validate(value).is_ok(),
+      "{:?}",
+      value,
+    );
+  }
+}
+
+#[test]
+fn validate_errors() {
+  let value = user(vec![
+    ("name", Value::Object(btreemap! {})),
+    ("age", Value::String("42".into())),
+    (
+      "tags",
+      Value::Array(vec![Value::String("admin".into()), Value::Int64(1)]),
+    ),
+    ("extra", Value::Boolean(true)),
+  ]);
+
+  let errors = User::convex_schema().validate(&value).unwrap_err();
+  assert_eq!(
+    vec![
+      ValidationError {
+        path: "".into(),
+        message: "Unexpected fields: extra".into(),
+      },
+      ValidationError {
+        path: "name".into(),
+        message: "Missing required field".into(),
+      },
+      ValidationError { path: "age".into(), message: "Expected an int".into() },
+      ValidationError {
+        path: "tags[1]".into(),
+        message: "Expected a string".into(),
+      },
+    ],
+    errors.0,
+  );
+  assert_eq!(
+    "Unexpected fields: extra, name: Missing required field, age: Expected an \
+     int, tags[1]: Expected a string",
+    errors.to_string(),
+  );
+}
+
+#[test]
+fn validate_dynamic_validator() {
+  let validator = Validator::Object(ObjectValidator {
+    fields: vec![FieldValidator {
+      name: "event".into(),
+      validator: Validator::String,
+    }],
+    strict: false,
+    rest: None,
+  });
+
+  let value = Value::Object(btreemap! {
+    "event".into() => Value::String("push".into()),
+    "extra".into() => Value::Null,
+  });
+  assert!(validator.validate(&value).is_ok());
+  assert!(validator.validate(&Value::Null).is_err());
+}
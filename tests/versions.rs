@@ -0,0 +1,74 @@
+use convex::Value;
+use maplit::btreemap;
+use ragkit_convex_macros::convex_model;
+
+convex_model!(User v1 {
+  name: v.string(),
+});
+
+convex_model!(User v2 {
+  firstName: v.string(),
+  lastName: v.string(),
+});
+
+convex_model!(#[strict] User v3 {
+  firstName: v.string(),
+  lastName: v.string(),
+  email: v.optional(v.string()),
+});
+
+impl From<UserV1> for UserV2 {
+  fn from(user: UserV1) -> Self {
+    let (first, last) = user.name.split_once(' ').unwrap_or((&user.name, ""));
+    UserV2::new(first.into(), last.into())
+  }
+}
+
+impl From<UserV2> for UserV3 {
+  fn from(user: UserV2) -> Self {
+    UserV3::new(user.firstName, user.lastName)
+  }
+}
+
+#[test]
+fn read_latest_version() {
+  let value = Value::Object(btreemap! {
+    "firstName".into() => Value::String("Alice".into()),
+    "lastName".into() => Value::String("Smith".into()),
+    "email".into() => Value::String("alice@example.com".into()),
+  });
+
+  let (user, version) = UserV3::from_any_version(&value).unwrap();
+  assert_eq!(3, version);
+  assert_eq!(Some("alice@example.com".into()), user.email);
+  assert_eq!(3, UserV3::VERSION);
+}
+
+#[test]
+fn upgrade_older_versions() {
+  let value = Value::Object(btreemap! {
+    "name".into() => Value::String("Alice Smith".into()),
+  });
+  let (user, version) = UserV3::from_any_version(&value).unwrap();
+  assert_eq!(1, version);
+  assert_eq!(UserV3::new("Alice".into(), "Smith".into()), user);
+
+  let value = Value::Object(btreemap! {
+    "firstName".into() => Value::String("Bob".into()),
+    "lastName".into() => Value::String("Jones".into()),
+    "nickname".into() => Value::String("Bobby".into()),
+  });
+  let (user, version) = UserV3::from_any_version(&value).unwrap();
+  assert_eq!(2, version);
+  assert_eq!(UserV3::new("Bob".into(), "Jones".into()), user);
+}
+
+#[test]
+fn upgrade_older_versions_negative() {
+  let value = Value::Object(btreemap! {
+    "name".into() => Value::Int64(42),
+  });
+  let error = UserV3::from_any_version(&value).unwrap_err();
+  // The error is from the latest version.
+  assert!(error.to_string().contains("UserV3"));
+}